@@ -26,6 +26,8 @@
 //! println!("{}", table);
 //! ```
 
+use std::sync::Arc;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -35,27 +37,365 @@ pub enum MarkdownTableError {
 
     #[error("Length of rows must be at least 1 when creating a table.")]
     NoRowsSpecified,
+
+    #[error("Row index {0} is out of range for a table with {1} rows.")]
+    RowOutOfRange(usize, usize),
+
+    #[error("Column index {0} is out of range for a row with {1} columns.")]
+    ColumnOutOfRange(usize, usize),
+
+    #[error("Invalid separator cell `{0}`.")]
+    InvalidSeparator(String),
+
+    #[error("Tables differ in shape, {0:?} and {1:?} (rows, columns).")]
+    ShapeMismatch((usize, usize), (usize, usize)),
+
+    #[error("The table has no header.")]
+    NoHeader,
+
+    #[error("No column is named `{0}`.")]
+    UnknownColumn(String),
+
+    #[error("Column count mismatch, expected {0} got {1}.")]
+    ColumnCountMismatch(usize, usize),
+
+    #[error("Invalid column group {0}..{1}, groups must be non-empty and can't overlap.")]
+    InvalidColumnGroup(usize, usize),
+
+    #[error("Too many columns, expected at most {0} got {1}.")]
+    TooManyColumns(usize, usize),
+
+    #[error("Headerless tables can't have a column header.")]
+    UnexpectedHeader,
+
+    #[error("Tables with a header need a header for every column.")]
+    MissingColumnHeader,
+
+    #[error("Link URLs can't be empty.")]
+    EmptyLinkUrl,
+
+    #[error("Unknown alignment `{0}`, expected one of `l`, `c`, `r`, `<`, `^`, `>` or `-`.")]
+    InvalidAlignment(char),
+
+    #[error(
+        "Column {0} can't be fixed at width {1}, narrower than its separator or the ellipsis."
+    )]
+    FixedWidthTooNarrow(usize, usize),
 }
 
 type Result<T> = std::result::Result<T, MarkdownTableError>;
 
+/// Horizontal alignment of a column, written as colons in the separator row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// No colons, leaving the alignment up to the renderer.
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn from_separator(cell: &str) -> Result<Self> {
+        let left = cell.starts_with(':');
+        let right = cell.len() > 1 && cell.ends_with(':');
+        let dashes = &cell[usize::from(left)..cell.len() - usize::from(right)];
+
+        if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+            return Err(MarkdownTableError::InvalidSeparator(cell.to_string()));
+        }
+
+        Ok(match (left, right) {
+            (true, true) => Alignment::Center,
+            (true, false) => Alignment::Left,
+            (false, true) => Alignment::Right,
+            (false, false) => Alignment::None,
+        })
+    }
+
+    /// Narrowest separator cell that still has a dash between its colons.
+    fn min_separator_width(self) -> usize {
+        match self {
+            Alignment::None => 1,
+            Alignment::Left | Alignment::Right => 2,
+            Alignment::Center => 3,
+        }
+    }
+
+    fn separator(self, width: usize) -> String {
+        match self {
+            Alignment::None => "-".repeat(width),
+            Alignment::Left => format!(":{}", "-".repeat(width.saturating_sub(1))),
+            Alignment::Center => format!(":{}:", "-".repeat(width.saturating_sub(2))),
+            Alignment::Right => format!("{}:", "-".repeat(width.saturating_sub(1))),
+        }
+    }
+
+    /// Spaces to put before and after content of `text_width` characters to fill `width`.
+    fn padding(self, text_width: usize, width: usize) -> (usize, usize) {
+        let total = width.saturating_sub(text_width);
+
+        match self {
+            Alignment::Right => (total, 0),
+            Alignment::Center => (total / 2, total - total / 2),
+            Alignment::None | Alignment::Left => (0, total),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct MarkdownTable {
     header: Option<TableRow>,
     rows: Vec<TableRow>,
+    trim_trailing: bool,
+    repeat_header_every: usize,
+    super_header: Option<TableRow>,
+    null_sentinel: Option<(String, String)>,
+    max_render_width: Option<usize>,
+    collapsible: Option<String>,
+    uniform_width: bool,
+    cell_links: std::collections::BTreeMap<(usize, usize), String>,
+    auto_footer: Option<AggregateKind>,
+    column_groups: Vec<(usize, usize, String)>,
+    column_wrap: usize,
+    wrap_key_column: Option<usize>,
+    cell_renderer: Option<CellRenderer>,
+    tab_width: usize,
+    force_separator: bool,
+    caption: Option<String>,
+    strip_ansi_for_width: bool,
+    empty_message: Option<String>,
+    footer_header: bool,
+    row_separators: bool,
+    ellipsis: String,
+    cell_colspans: std::collections::BTreeMap<(usize, usize), usize>,
+    nbsp_padding: bool,
+    column_origins: Vec<usize>,
+    plain_underline: bool,
+    emit_separator: bool,
+    columns: std::collections::BTreeMap<usize, ColumnOptions>,
 }
 
-impl std::fmt::Display for MarkdownTable {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(ref header) = self.header {
-            self.fmt_line(f, &|col, _len| header.0[col].clone())?;
-            self.fmt_line(f, &|_col, len| "-".repeat(len))?;
+/// Aggregate computed over the numeric cells of a column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateKind {
+    Sum,
+    Mean,
+    Max,
+    Min,
+    Count,
+}
+
+impl AggregateKind {
+    fn label(self) -> &'static str {
+        match self {
+            AggregateKind::Sum => "Sum",
+            AggregateKind::Mean => "Mean",
+            AggregateKind::Max => "Max",
+            AggregateKind::Min => "Min",
+            AggregateKind::Count => "Count",
         }
+    }
 
-        for row in &self.rows {
-            self.fmt_line(f, &|col, _len| row.0[col].clone())?;
+    fn apply(self, values: &[f64]) -> f64 {
+        match self {
+            AggregateKind::Sum => values.iter().sum(),
+            AggregateKind::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            AggregateKind::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            AggregateKind::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            AggregateKind::Count => values.len() as f64,
         }
+    }
+}
 
-        Ok(())
+/// Direction to sort rows in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Unit system used by [`MarkdownTable::set_scaled_column`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScaleBase {
+    /// Powers of 1024 with `KiB`, `MiB`, ... suffixes.
+    Binary,
+    /// Powers of 1000 with `k`, `M`, ... suffixes.
+    Decimal,
+}
+
+impl ScaleBase {
+    fn scale(self, value: f64) -> Option<String> {
+        let (base, suffixes) = match self {
+            ScaleBase::Binary => (1024.0, ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"]),
+            ScaleBase::Decimal => (1000.0, ["k", "M", "G", "T", "P", "E"]),
+        };
+
+        let mut scaled = value / base;
+        if scaled.abs() < 1.0 {
+            return None;
+        }
+
+        // Compare the value as it will be shown, so that 1023.96 moves up to 1.0 of the next unit
+        // rather than showing as 1024.0.
+        for suffix in &suffixes[..suffixes.len() - 1] {
+            if (scaled.abs() * 10.0).round() < base * 10.0 {
+                return Some(format!("{scaled:.1} {suffix}"));
+            }
+            scaled /= base;
+        }
+
+        Some(format!("{scaled:.1} {}", suffixes[suffixes.len() - 1]))
+    }
+}
+
+/// Counts of the differences between two tables, from [`MarkdownTable::diff_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub added_rows: usize,
+    pub removed_rows: usize,
+    pub changed_cells: usize,
+}
+
+/// The rendered sections of a table, from [`MarkdownTable::parts`], without line breaks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableParts {
+    pub header: Option<String>,
+    pub separator: Option<String>,
+    pub rows: Vec<String>,
+}
+
+/// Most specific type shared by the cells of a column, from [`MarkdownTable::infer_column_type`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Float,
+    Boolean,
+    Text,
+}
+
+/// Where the ellipsis goes when a cell is truncated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TruncatePos {
+    Start,
+    Middle,
+    #[default]
+    End,
+}
+
+/// Rendering settings for a single column.
+#[derive(Clone, Default)]
+struct ColumnOptions {
+    max_width: Option<usize>,
+    alignment: Alignment,
+    formatter: Option<CellFormatter>,
+    decimal_align: bool,
+    min_width: Option<usize>,
+    abbreviation: Option<String>,
+    collapse_repeats: bool,
+    width_percent: Option<u8>,
+    hidden: bool,
+    truncate_pos: TruncatePos,
+    padding: Option<(usize, usize)>,
+    row_numbers: bool,
+}
+
+type CellFormatter = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+type CellRenderer = Arc<dyn Fn(usize, usize, &str) -> String + Send + Sync>;
+
+/// Every cell as it is rendered, computed once per render so that settings spanning a whole
+/// column can see all of its cells.
+struct Grid {
+    super_header: Option<Vec<Cell>>,
+    header: Option<Vec<Cell>>,
+    rows: Vec<Vec<Cell>>,
+    footer: Option<Vec<Cell>>,
+}
+
+impl Grid {
+    fn lines(&self) -> impl Iterator<Item = &Vec<Cell>> {
+        self.super_header
+            .iter()
+            .chain(&self.header)
+            .chain(&self.rows)
+            .chain(&self.footer)
+    }
+
+    fn lines_mut(&mut self) -> impl Iterator<Item = &mut Vec<Cell>> {
+        self.super_header
+            .iter_mut()
+            .chain(&mut self.header)
+            .chain(&mut self.rows)
+            .chain(&mut self.footer)
+    }
+}
+
+/// A rendered cell and the width it takes up, which is smaller than its length when part of it
+/// is markup that renderers hide.
+#[derive(Clone)]
+struct Cell {
+    text: String,
+    width: usize,
+}
+
+impl Cell {
+    fn new(text: String) -> Self {
+        let width = text_width(&text);
+
+        Self { text, width }
+    }
+}
+
+impl std::fmt::Display for MarkdownTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.render_fmt(f)
+    }
+}
+
+impl TryFrom<Vec<Vec<String>>> for MarkdownTable {
+    type Error = MarkdownTableError;
+
+    /// Builds a table from a grid whose first row is the header.
+    fn try_from(grid: Vec<Vec<String>>) -> Result<Self> {
+        let mut grid = grid.into_iter();
+        let header = grid.next().ok_or(MarkdownTableError::NoRowsSpecified)?;
+
+        Self::new(Some(header), grid.collect())
+    }
+}
+
+impl<K, V> TryFrom<std::collections::HashMap<K, V>> for MarkdownTable
+where
+    K: std::fmt::Display + Ord,
+    V: std::fmt::Display,
+{
+    type Error = MarkdownTableError;
+
+    /// Builds a headerless two-column table of keys and values, sorted by key.
+    ///
+    /// Fails with [`MarkdownTableError::NoRowsSpecified`] for an empty map.
+    fn try_from(map: std::collections::HashMap<K, V>) -> Result<Self> {
+        let mut entries: Vec<(K, V)> = map.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let rows = entries
+            .into_iter()
+            .map(|(key, value)| TableRow::new(vec![key.to_string(), value.to_string()]))
+            .collect();
+
+        Self::new(None::<TableRow>, rows)
+    }
+}
+
+impl IntoIterator for MarkdownTable {
+    type Item = TableRow;
+    type IntoIter = std::vec::IntoIter<TableRow>;
+
+    /// Consumes the table, yielding its data rows without the header.
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
     }
 }
 
@@ -67,6 +407,10 @@ impl MarkdownTable {
         let header = header.map(|v| v.into());
         let rows: Vec<TableRow> = rows.into_iter().map(|v| v.into()).collect();
 
+        if header.is_none() && rows.is_empty() {
+            return Err(MarkdownTableError::NoRowsSpecified);
+        }
+
         for row in &rows {
             Self::validate_row_length(
                 &header.clone().or_else(|| Some(rows[0].clone())).unwrap(),
@@ -74,180 +418,4930 @@ impl MarkdownTable {
             )?;
         }
 
-        Ok(Self { header, rows })
+        Ok(Self::from_parts(header, rows))
     }
 
-    pub fn add_row(&mut self, row: impl Into<TableRow>) -> Result<()> {
-        let row = row.into();
-        Self::validate_row_length(
-            &self
-                .header
-                .clone()
-                .or_else(|| Some(self.rows[0].clone()))
-                .unwrap(),
-            &row,
-        )?;
-        self.rows.push(row);
+    /// Builds a table whose header, if any, and rows must all have exactly `expected` columns.
+    pub fn with_columns(
+        expected: usize,
+        header: Option<impl Into<TableRow>>,
+        rows: Vec<impl Into<TableRow>>,
+    ) -> Result<Self> {
+        let header = header.map(|v| v.into());
+        let rows: Vec<TableRow> = rows.into_iter().map(|v| v.into()).collect();
 
-        Ok(())
+        if header.is_none() && rows.is_empty() {
+            return Err(MarkdownTableError::NoRowsSpecified);
+        }
+
+        for row in header.iter().chain(&rows) {
+            if row.len() != expected {
+                return Err(MarkdownTableError::InvalidRowLength(expected, row.len()));
+            }
+        }
+
+        Ok(Self::from_parts(header, rows))
     }
 
-    fn cols(&self) -> usize {
-        if let Some(ref header) = self.header {
-            header.0.len()
-        } else {
-            self.rows[0].0.len()
+    /// Starts building a table step by step.
+    pub fn builder() -> MarkdownTableBuilder {
+        MarkdownTableBuilder::default()
+    }
+
+    /// Builds a table without a header, sparing `new` a type annotation on its `None`.
+    pub fn headerless(rows: Vec<impl Into<TableRow>>) -> Result<Self> {
+        Self::new(None::<TableRow>, rows)
+    }
+
+    /// Builds a table from a fallible source of rows, stopping at the first error.
+    ///
+    /// Errors from the source are returned as-is, while invalid rows are converted into the
+    /// caller's error type through its `From<MarkdownTableError>` implementation.
+    pub fn try_from_iter<I, T, E>(
+        header: Option<impl Into<TableRow>>,
+        iter: I,
+    ) -> std::result::Result<Self, E>
+    where
+        I: IntoIterator<Item = std::result::Result<T, E>>,
+        T: Into<TableRow>,
+        E: From<MarkdownTableError>,
+    {
+        let rows = iter
+            .into_iter()
+            .collect::<std::result::Result<Vec<T>, E>>()?;
+
+        Ok(Self::new(header, rows)?)
+    }
+
+    /// Builds a two-column table from pairs, formatting each field with `Display`.
+    pub fn from_tuples2<A, B, I>(header: Option<impl Into<TableRow>>, iter: I) -> Result<Self>
+    where
+        A: std::fmt::Display,
+        B: std::fmt::Display,
+        I: IntoIterator<Item = (A, B)>,
+    {
+        let rows: Vec<TableRow> = iter
+            .into_iter()
+            .map(|(a, b)| TableRow::new(vec![a.to_string(), b.to_string()]))
+            .collect();
+
+        Self::new(header, rows)
+    }
+
+    /// Builds a three-column table from triples, formatting each field with `Display`.
+    pub fn from_tuples3<A, B, C, I>(header: Option<impl Into<TableRow>>, iter: I) -> Result<Self>
+    where
+        A: std::fmt::Display,
+        B: std::fmt::Display,
+        C: std::fmt::Display,
+        I: IntoIterator<Item = (A, B, C)>,
+    {
+        let rows: Vec<TableRow> = iter
+            .into_iter()
+            .map(|(a, b, c)| TableRow::new(vec![a.to_string(), b.to_string(), c.to_string()]))
+            .collect();
+
+        Self::new(header, rows)
+    }
+
+    /// Builds a two-column table of each item yielded by `iter` and its index, counting from
+    /// `start`.
+    pub fn from_enumerated<I, T>(header: (&str, &str), iter: I, start: usize) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: std::fmt::Display,
+    {
+        let rows = iter
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                TableRow::new(vec![(start + index).to_string(), value.to_string()])
+            })
+            .collect();
+
+        Self::from_parts(Some(TableRow::from(&[header.0, header.1])), rows)
+    }
+
+    /// Stacks the data rows of `tables` into one headerless table with default settings.
+    ///
+    /// Headers are dropped, and every table must have the same number of columns.
+    pub fn concat(tables: Vec<MarkdownTable>) -> Result<MarkdownTable> {
+        let expected = tables
+            .first()
+            .ok_or(MarkdownTableError::NoRowsSpecified)?
+            .cols();
+
+        let mut rows = Vec::new();
+        for table in tables {
+            if table.cols() != expected {
+                return Err(MarkdownTableError::ColumnCountMismatch(
+                    expected,
+                    table.cols(),
+                ));
+            }
+            rows.extend(table.rows);
         }
+
+        if rows.is_empty() {
+            return Err(MarkdownTableError::NoRowsSpecified);
+        }
+
+        Ok(Self::from_parts(None, rows))
     }
 
-    fn col_len(&self, col: usize) -> Option<usize> {
-        if col >= self.cols() {
-            None
-        } else {
-            let col_len = self.rows.iter().fold(0, |acc, curr| {
-                if curr.col_len(col) > acc {
-                    curr.col_len(col)
-                } else {
-                    acc
+    /// Builds a table with default settings from rows that are already validated.
+    fn from_parts(header: Option<TableRow>, rows: Vec<TableRow>) -> Self {
+        Self {
+            header,
+            rows,
+            trim_trailing: false,
+            repeat_header_every: 0,
+            super_header: None,
+            null_sentinel: None,
+            max_render_width: None,
+            collapsible: None,
+            uniform_width: false,
+            cell_links: std::collections::BTreeMap::new(),
+            auto_footer: None,
+            column_groups: Vec::new(),
+            column_wrap: 0,
+            wrap_key_column: None,
+            cell_renderer: None,
+            tab_width: 4,
+            force_separator: false,
+            caption: None,
+            strip_ansi_for_width: false,
+            empty_message: None,
+            footer_header: false,
+            row_separators: false,
+            ellipsis: "…".to_string(),
+            cell_colspans: std::collections::BTreeMap::new(),
+            nbsp_padding: false,
+            column_origins: Vec::new(),
+            plain_underline: true,
+            emit_separator: true,
+            columns: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Parses a markdown table, reading column alignments from its separator row.
+    ///
+    /// Input without a separator row below its first line is read as a headerless table.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut lines = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(split_markdown_row);
+        let first = lines.next().ok_or(MarkdownTableError::NoRowsSpecified)?;
+        let rest: Vec<Vec<String>> = lines.collect();
+
+        match rest.first() {
+            Some(separator) if is_separator_row(separator) => {
+                let alignments = separator
+                    .iter()
+                    .map(|cell| Alignment::from_separator(cell))
+                    .collect::<Result<Vec<_>>>()?;
+                if alignments.len() != first.len() {
+                    return Err(MarkdownTableError::InvalidRowLength(
+                        first.len(),
+                        alignments.len(),
+                    ));
                 }
-            });
 
-            if let Some(ref header) = self.header {
-                if col_len > header.0[col].len() {
-                    Some(col_len)
-                } else {
-                    Some(header.0[col].len())
+                let mut table = Self::new(Some(first), rest[1..].to_vec())?;
+                for (col, alignment) in alignments.into_iter().enumerate() {
+                    table.set_alignment(col, alignment);
                 }
-            } else {
-                Some(col_len)
+
+                Ok(table)
             }
+            _ => Self::new(
+                None::<TableRow>,
+                std::iter::once(first).chain(rest).collect(),
+            ),
         }
     }
 
-    fn fmt_line(
-        &self,
-        f: &mut std::fmt::Formatter<'_>,
-        pred: &dyn Fn(usize, usize) -> String,
-    ) -> std::fmt::Result {
-        for col in 0..self.cols() {
-            let len = self.col_len(col).unwrap_or(0);
-            write!(f, "| {text:width$} ", text = pred(col, len), width = len)?;
+    pub fn add_row(&mut self, row: impl Into<TableRow>) -> Result<()> {
+        let row = row.into();
+        if let Some(expected) = self.header.as_ref().or_else(|| self.rows.first()) {
+            Self::validate_row_length(expected, &row)?;
         }
-        writeln!(f, "|")
+        self.rows.push(row);
+
+        Ok(())
     }
 
-    fn validate_row_length(header: &TableRow, new_row: &TableRow) -> Result<()> {
-        let header_len = header.0.len();
-        let new_len = new_row.0.len();
+    /// When enabled, the last column isn't padded to its full width, which avoids trailing
+    /// whitespace in the rendered lines.
+    pub fn set_trim_trailing(&mut self, trim_trailing: bool) {
+        self.trim_trailing = trim_trailing;
+    }
 
-        if header_len != new_len {
-            Err(MarkdownTableError::InvalidRowLength(header_len, new_len))
-        } else {
-            Ok(())
+    /// Alignment of `col`, or `None` if the table has no such column.
+    pub fn column_alignment(&self, col: usize) -> Option<Alignment> {
+        (col < self.cols()).then(|| self.alignment(col))
+    }
+
+    /// Sets every column's alignment from a spec with one character per column, ignoring
+    /// whitespace.
+    ///
+    /// `l` or `<` aligns left, `c` or `^` centers, `r` or `>` aligns right and `-` leaves the
+    /// alignment unspecified, so `"l r c"` and `"<>^"` are equivalent.
+    pub fn set_alignment_spec(&mut self, spec: &str) -> Result<()> {
+        let alignments = spec
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c {
+                'l' | '<' => Ok(Alignment::Left),
+                'c' | '^' => Ok(Alignment::Center),
+                'r' | '>' => Ok(Alignment::Right),
+                '-' => Ok(Alignment::None),
+                _ => Err(MarkdownTableError::InvalidAlignment(c)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if alignments.len() != self.cols() {
+            return Err(MarkdownTableError::ColumnCountMismatch(
+                self.cols(),
+                alignments.len(),
+            ));
+        }
+
+        for (col, alignment) in alignments.into_iter().enumerate() {
+            self.set_alignment(col, alignment);
         }
+
+        Ok(())
     }
-}
 
-#[derive(Clone)]
-pub struct TableRow(Vec<String>);
+    /// Adds a bold row above the header for labelling groups of columns.
+    ///
+    /// Markdown tables only have a single header, so this is rendered as a regular row.
+    pub fn set_super_header(&mut self, super_header: impl Into<TableRow>) -> Result<()> {
+        let super_header = super_header.into();
+        if super_header.len() != self.cols() {
+            return Err(MarkdownTableError::InvalidRowLength(
+                self.cols(),
+                super_header.len(),
+            ));
+        }
 
-impl TableRow {
-    pub fn new(data: Vec<String>) -> Self {
-        Self(data)
+        self.super_header = Some(super_header);
+
+        Ok(())
     }
 
-    fn col_len(&self, col: usize) -> usize {
-        self.0[col].len()
+    /// Shows short labels in the header for the given columns, with a legend below the table
+    /// mapping each label to the full header.
+    ///
+    /// Replaces any previously set abbreviations. Has no effect on tables without a header.
+    pub fn set_header_abbreviations(&mut self, map: Vec<(usize, String)>) {
+        for options in self.columns.values_mut() {
+            options.abbreviation = None;
+        }
+
+        for (col, abbreviation) in map {
+            self.column_options_mut(col).abbreviation = Some(abbreviation);
+        }
     }
-}
 
-impl<T: std::fmt::Display, const N: usize> From<&[T; N]> for TableRow {
-    fn from(value: &[T; N]) -> Self {
-        Self::new(value.iter().map(|v| v.to_string()).collect())
+    /// Repeats the header and separator lines after every `n` data rows, or never when `n` is 0.
+    ///
+    /// Has no effect on tables without a header.
+    pub fn set_repeat_header_every(&mut self, n: usize) {
+        self.repeat_header_every = n;
     }
-}
 
-impl<T: std::fmt::Display> From<&[T]> for TableRow {
-    fn from(value: &[T]) -> Self {
-        Self::new(value.iter().map(|v| v.to_string()).collect())
+    /// Measures cells by their visible width, ignoring ANSI escape sequences, while still
+    /// rendering them verbatim.
+    pub fn set_strip_ansi_for_width(&mut self, strip: bool) {
+        self.strip_ansi_for_width = strip;
     }
-}
 
-impl<T: std::fmt::Display> From<Vec<T>> for TableRow {
-    fn from(value: Vec<T>) -> Self {
-        Self::new(value.iter().map(|v| v.to_string()).collect())
+    /// Renders a single row with `message` in its first cell when the table has no data rows.
+    pub fn set_empty_message(&mut self, message: impl Into<String>) {
+        self.empty_message = Some(message.into());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Emits a separator line as the first line of headerless tables, for parsers that expect one.
+    pub fn set_force_separator(&mut self, force: bool) {
+        self.force_separator = force;
+    }
 
-    #[derive(Clone)]
-    struct DummyRow {
-        data: Vec<String>,
+    /// Renders tables wider than `max_cols` columns as stacked sub-tables of at most `max_cols`
+    /// columns each, or as a single table when `max_cols` is 0.
+    pub fn set_column_wrap(&mut self, max_cols: usize) {
+        self.column_wrap = max_cols;
     }
 
-    impl From<DummyRow> for TableRow {
-        fn from(val: DummyRow) -> Self {
-            TableRow(val.data)
-        }
+    /// Repeats `col` as the first column of every sub-table when wrapping columns.
+    pub fn set_wrap_key_column(&mut self, col: usize) {
+        self.wrap_key_column = Some(col);
     }
 
-    impl From<&DummyRow> for TableRow {
-        fn from(val: &DummyRow) -> Self {
-            TableRow(val.data.clone())
-        }
+    /// Expands tabs in markdown output to the next multiple of `n` characters within their cell,
+    /// 4 by default, or leaves them as they are when `n` is 0.
+    pub fn set_tab_width(&mut self, n: usize) {
+        self.tab_width = n;
     }
 
-    #[test]
-    fn adding_row() {
-        let dd = DummyRow {
-            data: vec!["a".to_string(), "b".to_string()],
-        };
+    /// Fills out cells to their column width with non-breaking spaces instead of ordinary ones, so
+    /// renderers that collapse runs of spaces keep the columns aligned.
+    pub fn set_nbsp_padding(&mut self, enabled: bool) {
+        self.nbsp_padding = enabled;
+    }
 
-        let mut mt =
-            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), vec![dd]).unwrap();
+    /// Puts a separator line between every pair of data rows, for reading the markdown as plain
+    /// text.
+    ///
+    /// GFM renderers end a table at the extra separators, so rendered output breaks apart.
+    pub fn set_row_separators(&mut self, enabled: bool) {
+        self.row_separators = enabled;
+    }
 
-        let res = mt.add_row(DummyRow {
-            data: vec!["c".to_string(), "d".to_string()],
-        });
+    /// Repeats the separator and header lines after the last data row.
+    ///
+    /// Has no effect on tables without a header. The separator is left out along with the one
+    /// below the header when [`MarkdownTable::set_emit_separator`] disables it.
+    pub fn set_footer_header(&mut self, enabled: bool) {
+        self.footer_header = enabled;
+    }
 
-        assert!(res.is_ok());
+    /// Whether to emit the separator line below the header, enabled by default.
+    ///
+    /// Disabling it suits markdown dialects that don't understand the separator row, at the
+    /// cost of the output no longer being a GFM table.
+    pub fn set_emit_separator(&mut self, emit_separator: bool) {
+        self.emit_separator = emit_separator;
     }
 
-    #[test]
-    fn wrong_row_size() {
-        let dd = DummyRow {
-            data: vec!["a".to_string(), "b".to_string()],
-        };
+    /// Whether [`MarkdownTable::to_plain`] underlines the header with dashes, enabled by default.
+    pub fn set_plain_underline(&mut self, enabled: bool) {
+        self.plain_underline = enabled;
+    }
 
-        let mut mt =
-            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), vec![dd]).unwrap();
+    /// Wraps the table in a collapsible `<details>` block with `summary` as its label, as
+    /// supported by GitHub.
+    pub fn set_collapsible(&mut self, summary: impl Into<String>) {
+        self.collapsible = Some(summary.into());
+    }
 
-        let res = mt.add_row(DummyRow {
-            data: vec!["d".to_string()],
-        });
+    /// When enabled, every column is as wide as the widest one.
+    pub fn set_uniform_width(&mut self, uniform_width: bool) {
+        self.uniform_width = uniform_width;
+    }
 
-        assert!(res.is_err());
+    /// Adds a footer row below a separator showing `kind` for every numeric column.
+    ///
+    /// The first non-numeric column is labelled with the aggregate's name and the others are
+    /// left blank.
+    pub fn set_auto_footer(&mut self, kind: AggregateKind) {
+        self.auto_footer = Some(kind);
     }
 
-    #[test]
-    fn to_string() {
-        let dd = vec![
-            DummyRow {
-                data: vec!["a".to_string(), "b".to_string()],
-            },
-            DummyRow {
-                data: vec!["c".to_string(), "d".to_string()],
-            },
-            DummyRow {
-                data: vec!["e".to_string(), "fg".to_string()],
-            },
-        ];
+    /// Sets the alignment of `col`, used for both its separator cell and its padding.
+    pub fn set_alignment(&mut self, col: usize, alignment: Alignment) {
+        self.column_options_mut(col).alignment = alignment;
+    }
+
+    /// Pads `col` to at least `width` characters.
+    pub fn set_min_column_width(&mut self, col: usize, width: usize) {
+        self.column_options_mut(col).min_width = Some(width);
+    }
+
+    /// Pins every column to exactly the matching entry of `widths`, truncating longer cells and
+    /// padding shorter ones.
+    ///
+    /// The widths are set as both minimum and maximum widths, and can be cleared again with
+    /// [`MarkdownTable::reset_column_constraints`].
+    ///
+    /// Fails with [`MarkdownTableError::FixedWidthTooNarrow`] for widths that can't hold the
+    /// column's separator or the ellipsis, so set the alignment and ellipsis first.
+    pub fn set_fixed_widths(&mut self, widths: Vec<usize>) -> Result<()> {
+        let cols = self.cols();
+        if widths.len() != cols {
+            return Err(MarkdownTableError::ColumnCountMismatch(cols, widths.len()));
+        }
+
+        let ellipsis_width = text_width(&self.ellipsis);
+        if let Some((col, &width)) = widths.iter().enumerate().find(|&(col, &width)| {
+            width < ellipsis_width.max(self.alignment(col).min_separator_width())
+        }) {
+            return Err(MarkdownTableError::FixedWidthTooNarrow(col, width));
+        }
+
+        for (col, width) in widths.into_iter().enumerate() {
+            self.set_max_column_width(col, width);
+            self.set_min_column_width(col, width);
+        }
+
+        Ok(())
+    }
+
+    /// Clears every column's minimum and maximum width, so widths follow the content again.
+    pub fn reset_column_constraints(&mut self) {
+        for options in self.columns.values_mut() {
+            options.min_width = None;
+            options.max_width = None;
+        }
+    }
+
+    /// Caps every column at `width` characters, whatever its own settings, to keep rendering
+    /// untrusted content from producing enormous output.
+    ///
+    /// The cap also overrides minimum and uniform widths, though columns stay wide enough for
+    /// their separator's alignment colons. Ellipses wider than the cap are cut down to fit.
+    ///
+    /// Use [`MarkdownTable::clamped_columns`] to find out which columns were cut short.
+    pub fn set_max_render_width(&mut self, width: usize) {
+        self.max_render_width = Some(width.max(1));
+    }
+
+    /// Columns with content wider than the cap set by [`MarkdownTable::set_max_render_width`].
+    pub fn clamped_columns(&self) -> Vec<usize> {
+        let Some(cap) = self.max_render_width else {
+            return Vec::new();
+        };
+
+        (0..self.cols())
+            .filter(|&col| {
+                self.row_indices()
+                    .any(|row| text_width(&self.cell_content(row, col)) > cap)
+            })
+            .collect()
+    }
+
+    /// Gives this table and every table in `others` the same column widths, the widest of each
+    /// column across all of them.
+    ///
+    /// The widths are pinned as minimum widths, so content added afterwards can still widen a
+    /// single table.
+    pub fn align_widths_with(&mut self, others: &mut [MarkdownTable]) -> Result<()> {
+        let cols = self.cols();
+        if let Some(other) = others.iter().find(|other| other.cols() != cols) {
+            return Err(MarkdownTableError::ColumnCountMismatch(cols, other.cols()));
+        }
+
+        let mut widths = self.col_lens(&self.grid());
+        for other in others.iter() {
+            for (width, other_width) in widths.iter_mut().zip(other.col_lens(&other.grid())) {
+                *width = (*width).max(other_width);
+            }
+        }
+
+        for table in std::iter::once(self).chain(others.iter_mut()) {
+            for (col, &width) in widths.iter().enumerate() {
+                table.set_min_column_width(col, width);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders data cells whose content is exactly `sentinel` as `display_as`, leaving the stored
+    /// content untouched.
+    pub fn set_null_sentinel(
+        &mut self,
+        sentinel: impl Into<String>,
+        display_as: impl Into<String>,
+    ) {
+        self.null_sentinel = Some((sentinel.into(), display_as.into()));
+    }
+
+    /// Makes the data cell at `row` and `col` span `span` columns in HTML output, leaving out the
+    /// cells it covers.
+    ///
+    /// Markdown can't span columns, so markdown output still renders every cell. A `span` of 0 or
+    /// 1 removes the span.
+    pub fn set_cell_colspan(&mut self, row: usize, col: usize, span: usize) -> Result<()> {
+        let rows = self.rows.len();
+        let cols = self.cols();
+        if row >= rows {
+            return Err(MarkdownTableError::RowOutOfRange(row, rows));
+        }
+        if col + span.max(1) > cols {
+            return Err(MarkdownTableError::ColumnOutOfRange(
+                col + span.max(1) - 1,
+                cols,
+            ));
+        }
+
+        if span > 1 {
+            self.cell_colspans.insert((row, col), span);
+        } else {
+            self.cell_colspans.remove(&(row, col));
+        }
+
+        Ok(())
+    }
+
+    /// Captions the table with `caption` in HTML output.
+    pub fn set_caption(&mut self, caption: impl Into<String>) {
+        self.caption = Some(caption.into());
+    }
+
+    /// Groups the columns `start..end` under a spanning `label` above the header in HTML output.
+    ///
+    /// Markdown has no way to span columns, so groups don't show up in markdown output. Use
+    /// [`MarkdownTable::set_super_header`] to label groups there.
+    pub fn set_column_group(
+        &mut self,
+        start: usize,
+        end: usize,
+        label: impl Into<String>,
+    ) -> Result<()> {
+        let overlaps = self
+            .column_groups
+            .iter()
+            .any(|&(other_start, other_end, _)| start < other_end && other_start < end);
+        if start >= end || overlaps {
+            return Err(MarkdownTableError::InvalidColumnGroup(start, end));
+        }
+        if end > self.cols() {
+            return Err(MarkdownTableError::ColumnOutOfRange(end - 1, self.cols()));
+        }
+
+        self.column_groups.push((start, end, label.into()));
+        self.column_groups.sort_by_key(|&(start, _, _)| start);
+
+        Ok(())
+    }
+
+    /// Sets the relative width of `col` in HTML output, emitted as a `<col>` element.
+    ///
+    /// Percentages don't need to add up to 100, browsers scale them as needed.
+    pub fn set_column_width_percent(&mut self, col: usize, pct: u8) {
+        self.column_options_mut(col).width_percent = Some(pct);
+    }
+
+    /// Renders the data cell at `row` and `col` as a markdown link to `url`.
+    ///
+    /// The column is only as wide as the link's label. Links stay attached to the position,
+    /// not to the row, if rows are reordered afterwards.
+    pub fn set_cell_link(&mut self, row: usize, col: usize, url: impl Into<String>) -> Result<()> {
+        let url = url.into();
+        if url.trim().is_empty() {
+            return Err(MarkdownTableError::EmptyLinkUrl);
+        }
+
+        let len = self.rows.len();
+        let cells = self
+            .rows
+            .get(row)
+            .ok_or(MarkdownTableError::RowOutOfRange(row, len))?
+            .len();
+        if col >= cells {
+            return Err(MarkdownTableError::ColumnOutOfRange(col, cells));
+        }
+
+        self.cell_links
+            .insert((row, col), url.replace('(', "\\(").replace(')', "\\)"));
+
+        Ok(())
+    }
+
+    /// Formats every data cell of `col` with `formatter` when rendering, leaving the stored
+    /// content untouched.
+    pub fn set_column_formatter(
+        &mut self,
+        col: usize,
+        formatter: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) {
+        self.column_options_mut(col).formatter = Some(Arc::new(formatter));
+    }
+
+    /// Renders every data cell with `renderer`, called with the cell's row, column and formatted
+    /// content, leaving the stored content untouched.
+    pub fn set_cell_renderer<F>(&mut self, renderer: F)
+    where
+        F: Fn(usize, usize, &str) -> String + Send + Sync + 'static,
+    {
+        self.cell_renderer = Some(Arc::new(renderer));
+    }
+
+    /// Renders `col` as task-list checkboxes, `[x]` for `true`/`1` and `[ ]` for `false`/`0`.
+    ///
+    /// Other values are rendered unchanged.
+    pub fn set_checkbox_column(&mut self, col: usize) {
+        self.set_column_formatter(col, |text| match text.trim().to_lowercase().as_str() {
+            "true" | "1" => "[x]".to_string(),
+            "false" | "0" => "[ ]".to_string(),
+            _ => text.to_string(),
+        });
+    }
+
+    /// Inserts `separator` between every three digits of the integer part of numeric cells in
+    /// `col`, rendering `1234567` as `1,234,567`.
+    ///
+    /// Other values are rendered unchanged.
+    pub fn set_number_grouping(&mut self, col: usize, separator: char) {
+        self.set_column_formatter(col, move |text| {
+            group_digits(text, separator).unwrap_or_else(|| text.to_string())
+        });
+    }
+
+    /// Renders the cells of `col` as inline code, using a longer backtick span for cells that
+    /// contain backticks themselves.
+    pub fn set_code_column(&mut self, col: usize) {
+        self.set_column_formatter(col, code_span);
+    }
+
+    /// Renders numeric cells in `col` of at least one unit of `base` with a unit suffix, like
+    /// `1536` as `1.5 KiB`.
+    ///
+    /// Other values are rendered unchanged.
+    pub fn set_scaled_column(&mut self, col: usize, base: ScaleBase) {
+        self.set_column_formatter(col, move |text| {
+            text.trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|value| base.scale(value))
+                .unwrap_or_else(|| text.to_string())
+        });
+    }
+
+    /// Left-pads integer cells in `col` with zeros to `width` digits, keeping any sign in front,
+    /// so `7` renders as `007` for a width of 3.
+    ///
+    /// Other values are rendered unchanged.
+    pub fn set_zero_pad_column(&mut self, col: usize, width: usize) {
+        self.set_column_formatter(col, move |text| {
+            let trimmed = text.trim();
+            let digits = trimmed.trim_start_matches(['+', '-']);
+            let sign = &trimmed[..trimmed.len() - digits.len()];
+
+            if sign.len() > 1 || digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return text.to_string();
+            }
+
+            format!("{sign}{digits:0>width$}")
+        });
+    }
+
+    /// Lines up the decimal points of the numeric cells in `col`, padding their integer and
+    /// fractional parts independently.
+    ///
+    /// The column is right-aligned, so non-numeric cells fall back to right alignment.
+    pub fn set_decimal_align(&mut self, col: usize) {
+        let options = self.column_options_mut(col);
+        options.decimal_align = true;
+        options.alignment = Alignment::Right;
+    }
+
+    /// Renders a ditto mark (`"`) in place of cells in `col` that repeat the cell directly above.
+    pub fn set_collapse_repeats(&mut self, col: usize) {
+        self.column_options_mut(col).collapse_repeats = true;
+    }
+
+    /// Shows or hides `col` in markdown and HTML output, keeping its data either way.
+    ///
+    /// Hidden columns are still exported by [`MarkdownTable::to_tsv`] and
+    /// [`MarkdownTable::to_json`].
+    pub fn set_column_visible(&mut self, col: usize, visible: bool) {
+        self.column_options_mut(col).hidden = !visible;
+    }
+
+    /// Puts `left` and `right` spaces between the content of `col` and its pipes, instead of one
+    /// on each side.
+    pub fn set_column_padding(&mut self, col: usize, left: usize, right: usize) {
+        self.column_options_mut(col).padding = Some((left, right));
+    }
+
+    /// Limits `col` to at most `width` characters, truncating longer cells with an ellipsis.
+    pub fn set_max_column_width(&mut self, col: usize, width: usize) {
+        self.column_options_mut(col).max_width = Some(width.max(1));
+    }
+
+    /// Limits `col` to at most `width` characters, cutting longer cells at `pos` and putting an
+    /// ellipsis in their place.
+    pub fn set_truncation(&mut self, col: usize, width: usize, pos: TruncatePos) {
+        let options = self.column_options_mut(col);
+        options.max_width = Some(width.max(1));
+        options.truncate_pos = pos;
+    }
+
+    /// Marks truncated cells with `ellipsis` instead of `…`.
+    pub fn set_ellipsis(&mut self, ellipsis: impl Into<String>) {
+        self.ellipsis = ellipsis.into();
+    }
+
+    /// Appends every row yielded by `iter`, or none of them if any row has the wrong length.
+    pub fn extend_from<I, T>(&mut self, iter: I) -> Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<TableRow>,
+    {
+        let rows: Vec<TableRow> = iter.into_iter().map(|v| v.into()).collect();
+
+        if let Some(expected) = self
+            .header
+            .as_ref()
+            .or_else(|| self.rows.first())
+            .or_else(|| rows.first())
+        {
+            for row in &rows {
+                Self::validate_row_length(expected, row)?;
+            }
+        }
+
+        self.rows.extend(rows);
+
+        Ok(())
+    }
+
+    /// The header row, if the table has one.
+    pub fn header(&self) -> Option<&TableRow> {
+        self.header.as_ref()
+    }
+
+    /// Renames the header of `col`.
+    pub fn set_column_header(&mut self, col: usize, label: impl Into<String>) -> Result<()> {
+        self.header
+            .as_mut()
+            .ok_or(MarkdownTableError::NoHeader)?
+            .set(col, label)
+    }
+
+    /// Index of the first column whose header is `name`.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.header.as_ref()?.0.iter().position(|cell| cell == name)
+    }
+
+    /// Content of the data cell at `row` and `col`.
+    pub fn get_cell(&self, row: usize, col: usize) -> Option<&str> {
+        self.rows.get(row)?.get(col)
+    }
+
+    /// Replaces the content of the data cell at `row` and `col`.
+    pub fn set_cell(&mut self, row: usize, col: usize, value: impl Into<String>) -> Result<()> {
+        let len = self.rows.len();
+
+        self.rows
+            .get_mut(row)
+            .ok_or(MarkdownTableError::RowOutOfRange(row, len))?
+            .set(col, value)
+    }
+
+    /// Stores `value` as the content of the data cell at `row` and `col`.
+    pub fn set_cell_typed<T>(&mut self, row: usize, col: usize, value: T) -> Result<()>
+    where
+        T: std::str::FromStr + std::fmt::Display,
+    {
+        self.set_cell(row, col, value.to_string())
+    }
+
+    /// Parses the content of the data cell at `row` and `col` as a `T`, or `None` if the cell
+    /// doesn't exist.
+    pub fn get_cell_typed<T: std::str::FromStr>(
+        &self,
+        row: usize,
+        col: usize,
+    ) -> Option<std::result::Result<T, T::Err>> {
+        self.get_cell(row, col).map(str::parse)
+    }
+
+    /// Content of the data cell at `row` in the column whose header is `column`.
+    pub fn get_by_name(&self, row: usize, column: &str) -> Option<&str> {
+        self.get_cell(row, self.column_index(column)?)
+    }
+
+    /// Replaces the content of the data cell at `row` in the column whose header is `column`.
+    pub fn set_by_name(
+        &mut self,
+        row: usize,
+        column: &str,
+        value: impl Into<String>,
+    ) -> Result<()> {
+        if self.header.is_none() {
+            return Err(MarkdownTableError::NoHeader);
+        }
+
+        let col = self
+            .column_index(column)
+            .ok_or_else(|| MarkdownTableError::UnknownColumn(column.to_string()))?;
+
+        self.set_cell(row, col, value)
+    }
+
+    /// The data row at `index` keyed by header names, or `None` if it's out of range or the
+    /// table has no header.
+    pub fn row_to_map(&self, index: usize) -> Option<std::collections::BTreeMap<String, String>> {
+        let header = self.header.as_ref()?;
+        let row = self.rows.get(index)?;
+
+        Some(
+            header
+                .0
+                .iter()
+                .cloned()
+                .zip(row.0.iter().cloned())
+                .collect(),
+        )
+    }
+
+    /// Converts every data row into a `T`, stopping at the first row that fails to convert.
+    pub fn records<T: FromTableRow>(&self) -> std::result::Result<Vec<T>, T::Error> {
+        self.rows.iter().map(T::from_table_row).collect()
+    }
+
+    /// Iterates over the data rows, excluding the header.
+    pub fn rows(&self) -> impl Iterator<Item = &TableRow> {
+        self.rows.iter()
+    }
+
+    /// Iterates mutably over the data rows, excluding the header.
+    ///
+    /// Cells can be edited in place, but every row must keep the table's column count.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut TableRow> {
+        self.rows.iter_mut()
+    }
+
+    /// Number of cells in the table, counting the header's cells when `include_header` is set.
+    pub fn cell_count(&self, include_header: bool) -> usize {
+        let rows = self.rows.len() + usize::from(include_header && self.header.is_some());
+
+        rows * self.cols()
+    }
+
+    /// Rough estimate of the heap memory held by the table's cells, in bytes.
+    ///
+    /// Counts the allocated capacity of every cell and of the vectors holding them, but not
+    /// rendering settings.
+    pub fn estimated_bytes(&self) -> usize {
+        let row_bytes = |row: &TableRow| {
+            row.0.capacity() * std::mem::size_of::<String>()
+                + row.0.iter().map(String::capacity).sum::<usize>()
+        };
+
+        std::mem::size_of::<Self>()
+            + self.rows.capacity() * std::mem::size_of::<TableRow>()
+            + self
+                .header
+                .iter()
+                .chain(&self.rows)
+                .map(row_bytes)
+                .sum::<usize>()
+    }
+
+    /// Computes `kind` over the data cells of `col`, skipping empty cells.
+    ///
+    /// Returns `None` if the column doesn't exist, has no numbers, or has cells that aren't
+    /// numbers.
+    pub fn aggregate(&self, col: usize, kind: AggregateKind) -> Option<f64> {
+        self.numeric_values(col).map(|values| kind.apply(&values))
+    }
+
+    /// Whether every non-empty data cell in `col` is a number, or `None` if the column doesn't
+    /// exist.
+    pub fn is_numeric_column(&self, col: usize) -> Option<bool> {
+        if col >= self.cols() {
+            return None;
+        }
+
+        Some(
+            self.rows
+                .iter()
+                .map(|row| row.0.get(col).map_or("", |cell| cell.trim()))
+                .all(|cell| cell.is_empty() || cell.parse::<f64>().is_ok()),
+        )
+    }
+
+    /// Coordinates as `(row, col)` of the data cells containing `needle`, row by row.
+    pub fn cells_matching(&self, needle: &str) -> Vec<(usize, usize)> {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .0
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| cell.contains(needle))
+                    .map(move |(col, _)| (row, col))
+            })
+            .collect()
+    }
+
+    /// Number of unique values among the data cells of `col`, or `None` if the column doesn't
+    /// exist.
+    pub fn distinct_count(&self, col: usize) -> Option<usize> {
+        if col >= self.cols() {
+            return None;
+        }
+
+        let values: std::collections::HashSet<&str> = self
+            .rows
+            .iter()
+            .map(|row| row.0.get(col).map_or("", String::as_str))
+            .collect();
+
+        Some(values.len())
+    }
+
+    /// Content of the widest data cell in `col`, the first one on ties, or `None` if the column
+    /// doesn't exist or there are no data rows.
+    pub fn widest_cell(&self, col: usize) -> Option<&str> {
+        if col >= self.cols() {
+            return None;
+        }
+
+        self.rows
+            .iter()
+            .rev()
+            .map(|row| row.0.get(col).map_or("", String::as_str))
+            .max_by_key(|cell| text_width(cell))
+    }
+
+    /// Most specific type of the non-empty data cells in `col`, `Text` when they're all empty,
+    /// or `None` if the column doesn't exist.
+    pub fn infer_column_type(&self, col: usize) -> Option<ColumnType> {
+        if col >= self.cols() {
+            return None;
+        }
+
+        let cells: Vec<&str> = self
+            .rows
+            .iter()
+            .map(|row| row.0.get(col).map_or("", |cell| cell.trim()))
+            .filter(|cell| !cell.is_empty())
+            .collect();
+        if cells.is_empty() {
+            return Some(ColumnType::Text);
+        }
+
+        let all = |pred: fn(&str) -> bool| cells.iter().all(|cell| pred(cell));
+        Some(if all(|cell| cell.parse::<i64>().is_ok()) {
+            ColumnType::Integer
+        } else if all(|cell| cell.parse::<f64>().is_ok()) {
+            ColumnType::Float
+        } else if all(|cell| {
+            cell.eq_ignore_ascii_case("true") || cell.eq_ignore_ascii_case("false")
+        }) {
+            ColumnType::Boolean
+        } else {
+            ColumnType::Text
+        })
+    }
+
+    /// Checks every data row against the table's column count, collecting all mismatches
+    /// instead of stopping at the first.
+    pub fn validate(&self) -> std::result::Result<(), Vec<MarkdownTableError>> {
+        let Some(expected) = self.header.as_ref().or_else(|| self.rows.first()) else {
+            return Ok(());
+        };
+
+        let errors: Vec<MarkdownTableError> = self
+            .rows
+            .iter()
+            .filter_map(|row| Self::validate_row_length(expected, row).err())
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Consumes the table, returning its data rows without the header.
+    pub fn take_rows(self) -> Vec<TableRow> {
+        self.rows
+    }
+
+    /// Consumes the table, returning its header and data rows.
+    pub fn into_parts(self) -> (Option<TableRow>, Vec<TableRow>) {
+        (self.header, self.rows)
+    }
+
+    /// Copies the header and data rows into plain vectors, the shape other table crates take.
+    pub fn to_rows_vec(&self) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+        (
+            self.header.as_ref().map(|header| header.0.clone()),
+            self.rows.iter().map(|row| row.0.clone()).collect(),
+        )
+    }
+
+    /// Keeps only the data rows for which `pred` returns `true`, leaving the header untouched.
+    pub fn retain_rows<F: FnMut(&TableRow) -> bool>(&mut self, pred: F) {
+        self.rows.retain(pred);
+    }
+
+    /// Removes the data rows whose cells are all empty or whitespace, returning how many were
+    /// removed.
+    pub fn drop_empty_rows(&mut self) -> usize {
+        let len = self.rows.len();
+        self.rows
+            .retain(|row| row.0.iter().any(|cell| !cell.trim().is_empty()));
+
+        len - self.rows.len()
+    }
+
+    /// Replaces every occurrence of `from` with `to` in the data cells, and in the header too
+    /// when `include_header` is set, returning the number of replacements.
+    pub fn replace_all(&mut self, from: &str, to: &str, include_header: bool) -> usize {
+        if from.is_empty() {
+            return 0;
+        }
+
+        let header = self.header.iter_mut().filter(|_| include_header);
+        let mut replaced = 0;
+
+        for cell in header.chain(&mut self.rows).flat_map(|row| &mut row.0) {
+            let count = cell.matches(from).count();
+            if count > 0 {
+                *cell = cell.replace(from, to);
+                replaced += count;
+            }
+        }
+
+        replaced
+    }
+
+    /// Swaps the data rows at indices `a` and `b`.
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> Result<()> {
+        let len = self.rows.len();
+
+        for index in [a, b] {
+            if index >= len {
+                return Err(MarkdownTableError::RowOutOfRange(index, len));
+            }
+        }
+
+        self.rows.swap(a, b);
+
+        Ok(())
+    }
+
+    /// Cyclically shifts the data rows down by `n` positions, or up when `n` is negative.
+    pub fn rotate_rows(&mut self, n: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+
+        let shift = n.unsigned_abs() % self.rows.len();
+        if n >= 0 {
+            self.rows.rotate_right(shift);
+        } else {
+            self.rows.rotate_left(shift);
+        }
+    }
+
+    /// Stably sorts the data rows by each of `keys` in turn, breaking ties with the next key.
+    ///
+    /// Cells that are both numbers are compared numerically, others as text. Numbers sort before
+    /// text.
+    pub fn sort_by_columns(&mut self, keys: &[(usize, SortOrder)]) -> Result<()> {
+        let cols = self.cols();
+        if let Some(&(col, _)) = keys.iter().find(|&&(col, _)| col >= cols) {
+            return Err(MarkdownTableError::ColumnOutOfRange(col, cols));
+        }
+
+        self.rows.sort_by(|a, b| {
+            keys.iter()
+                .map(|&(col, order)| {
+                    let ordering = compare_cells(
+                        a.0.get(col).map_or("", String::as_str),
+                        b.0.get(col).map_or("", String::as_str),
+                    );
+                    match order {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        Ok(())
+    }
+
+    /// Iterates over the rendered lines of the table, without their line breaks.
+    ///
+    /// Column widths depend on every row, so the table is rendered as a whole up front.
+    pub fn lines(&self) -> impl Iterator<Item = String> {
+        let rendered = self.to_string();
+        let lines: Vec<String> = rendered.lines().map(str::to_string).collect();
+
+        lines.into_iter()
+    }
+
+    /// Writes the rendered table to any `fmt::Write` implementor.
+    pub fn render_fmt<W: std::fmt::Write>(&self, w: &mut W) -> std::fmt::Result {
+        if let Some(ref summary) = self.collapsible {
+            write!(
+                w,
+                "<details><summary>{}</summary>\n\n",
+                escape_html(summary)
+            )?;
+        }
+
+        self.fmt_table(w)?;
+
+        if self.collapsible.is_some() {
+            write!(w, "\n</details>\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders each data row as its own line, padded to the table's column widths, without the
+    /// header or separator.
+    pub fn row_strings(&self) -> Vec<String> {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.row_strings();
+        }
+
+        let grid = self.grid();
+        let widths = self.col_lens(&grid);
+
+        grid.rows
+            .iter()
+            .map(|row| self.line_string(&widths, &|col, _len| row[col].clone()))
+            .collect()
+    }
+
+    /// Renders the table like `to_string`, but first checks that every row has the right length
+    /// and that no setting refers to a column or row that doesn't exist.
+    ///
+    /// `Display` renders such tables as best it can, silently ignoring the offending settings.
+    pub fn try_render(&self) -> Result<String> {
+        if let Err(mut errors) = self.validate() {
+            return Err(errors.remove(0));
+        }
+
+        let cols = self.cols();
+        let rows = self.rows.len();
+
+        let mut referenced_cols: Vec<usize> = self.columns.keys().copied().collect();
+        referenced_cols.extend(self.wrap_key_column);
+        referenced_cols.extend(self.column_groups.iter().map(|&(_, end, _)| end - 1));
+        if let Some(col) = referenced_cols.into_iter().find(|&col| col >= cols) {
+            return Err(MarkdownTableError::ColumnOutOfRange(col, cols));
+        }
+
+        for &(row, col) in self.cell_links.keys().chain(self.cell_colspans.keys()) {
+            if row >= rows {
+                return Err(MarkdownTableError::RowOutOfRange(row, rows));
+            }
+            if col >= cols {
+                return Err(MarkdownTableError::ColumnOutOfRange(col, cols));
+            }
+        }
+
+        Ok(self.to_string())
+    }
+
+    /// Renders the header, separator and data row lines separately, for assembling them with
+    /// custom spacing.
+    pub fn parts(&self) -> TableParts {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.parts();
+        }
+
+        let grid = self.grid();
+        let widths = self.col_lens(&grid);
+
+        let header = grid
+            .header
+            .as_ref()
+            .map(|header| self.line_string(&widths, &|col, _len| header[col].clone()));
+        let draws_separator = match header {
+            Some(_) => self.emit_separator,
+            None => self.force_separator,
+        };
+        let separator = draws_separator.then(|| {
+            self.line_string(&widths, &|col, len| {
+                Cell::new(self.alignment(col).separator(len))
+            })
+        });
+
+        TableParts {
+            header,
+            separator,
+            rows: grid
+                .rows
+                .iter()
+                .map(|row| self.line_string(&widths, &|col, _len| row[col].clone()))
+                .collect(),
+        }
+    }
+
+    /// Appends the rendered table to `buf`, so one buffer can be reused across renders.
+    pub fn render_into(&self, buf: &mut String) {
+        self.render_fmt(buf)
+            .expect("writing to a String can't fail");
+    }
+
+    /// Length in characters of a rendered line, including padding and pipes.
+    ///
+    /// With trailing trimming enabled, lines other than the separator may be shorter, and lines
+    /// containing cell links are longer since only the link labels are counted.
+    pub fn rendered_width(&self) -> usize {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.rendered_width();
+        }
+
+        let widths = self.col_lens(&self.grid());
+
+        widths.iter().sum::<usize>() + self.line_overhead(widths.len())
+    }
+
+    /// Caps column widths so that rendered lines are at most `width` characters long, shrinking
+    /// the widest columns first.
+    ///
+    /// Minimum widths of the shrunk columns are lowered to match. Columns never shrink below one
+    /// character, so very narrow widths may not be reachable.
+    pub fn fit_to_width(&mut self, width: usize) {
+        let visible_cols = self.visible_columns();
+        let visible = self.without_hidden_columns();
+        let table = visible.as_ref().unwrap_or(self);
+        let mut widths = table.col_lens(&table.grid());
+        let overhead = table.line_overhead(widths.len());
+        let budget = width.saturating_sub(overhead);
+
+        while widths.iter().sum::<usize>() > budget {
+            let Some((col, widest)) = widths
+                .iter_mut()
+                .enumerate()
+                .max_by_key(|(col, width)| (**width, std::cmp::Reverse(*col)))
+            else {
+                break;
+            };
+            if *widest <= 1 {
+                break;
+            }
+
+            *widest -= 1;
+            let max_width = *widest;
+            let options = self.column_options_mut(visible_cols[col]);
+            options.max_width = Some(max_width);
+            options.min_width = options.min_width.map(|min_width| min_width.min(max_width));
+        }
+    }
+
+    /// Fits the table to the width of the terminal, or to 80 characters when it's unknown, for
+    /// example because the output is piped.
+    #[cfg(feature = "terminal")]
+    pub fn fit_to_terminal(&mut self) {
+        let width = terminal_size::terminal_size().map(|(width, _)| usize::from(width.0));
+
+        self.fit_to_terminal_width(width);
+    }
+
+    #[cfg(feature = "terminal")]
+    fn fit_to_terminal_width(&mut self, width: Option<usize>) {
+        self.fit_to_width(width.unwrap_or(80));
+    }
+
+    /// Appends a column whose cell in each data row is `f` of that row.
+    ///
+    /// `header` labels the new column and must be given exactly when the table has a header.
+    pub fn add_computed_column<F: Fn(&TableRow) -> String>(
+        &mut self,
+        header: Option<String>,
+        f: F,
+    ) -> Result<()> {
+        match (&mut self.header, header) {
+            (Some(header_row), Some(header)) => header_row.0.push(header),
+            (Some(_), None) => return Err(MarkdownTableError::MissingColumnHeader),
+            (None, Some(_)) => return Err(MarkdownTableError::UnexpectedHeader),
+            (None, None) => {}
+        }
+
+        if let Some(ref mut super_header) = self.super_header {
+            super_header.0.push(String::new());
+        }
+        for row in &mut self.rows {
+            let value = f(row);
+            row.0.push(value);
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy of the table with a leading column numbering the data rows from 1.
+    ///
+    /// `header` labels the new column when the table has a header.
+    pub fn with_row_numbers(&self, header: &str) -> MarkdownTable {
+        let mut table = self.clone();
+
+        if let Some(ref mut header_row) = table.header {
+            header_row.0.insert(0, header.to_string());
+        }
+        if let Some(ref mut super_header) = table.super_header {
+            super_header.0.insert(0, String::new());
+        }
+        for (index, row) in table.rows.iter_mut().enumerate() {
+            row.0.insert(0, (index + 1).to_string());
+        }
+        table.columns = self
+            .columns
+            .iter()
+            .map(|(&col, options)| (col + 1, options.clone()))
+            .collect();
+        table.column_options_mut(0).row_numbers = true;
+        table.remap_cells(|row, col| Some((row, col + 1)));
+        for (start, end, _) in &mut table.column_groups {
+            *start += 1;
+            *end += 1;
+        }
+        table.wrap_key_column = self.wrap_key_column.map(|key| key + 1);
+
+        table
+    }
+
+    /// Returns a copy of the table keeping the first `head` and last `tail` data rows, separated
+    /// by a row of `…` cells.
+    ///
+    /// Tables with no more than `head + tail` rows are returned unchanged.
+    pub fn preview(&self, head: usize, tail: usize) -> MarkdownTable {
+        let mut table = self.clone();
+        let len = self.rows.len();
+        if len <= head.saturating_add(tail) {
+            return table;
+        }
+
+        let tail_start = len - tail;
+        let mut rows = self.rows[..head].to_vec();
+        rows.push(TableRow::new(vec!["…".to_string(); self.cols()]));
+        rows.extend_from_slice(&self.rows[tail_start..]);
+        table.rows = rows;
+
+        table.remap_cells(|row, col| match row {
+            row if row < head => Some((row, col)),
+            row if row >= tail_start => Some((row - tail_start + head + 1, col)),
+            _ => None,
+        });
+
+        table
+    }
+
+    /// Counts the rows added and removed and the cells changed from `self` to `other`.
+    ///
+    /// Rows are compared by position, so rows past the end of the shorter table count as added
+    /// or removed. Both tables must have the same number of columns.
+    pub fn diff_stats(&self, other: &MarkdownTable) -> Result<DiffStats> {
+        if self.cols() != other.cols() {
+            return Err(MarkdownTableError::ColumnCountMismatch(
+                self.cols(),
+                other.cols(),
+            ));
+        }
+
+        let changed_cells = self
+            .rows
+            .iter()
+            .zip(&other.rows)
+            .flat_map(|(old, new)| old.0.iter().zip(&new.0))
+            .filter(|(old, new)| old != new)
+            .count();
+
+        Ok(DiffStats {
+            added_rows: other.rows.len().saturating_sub(self.rows.len()),
+            removed_rows: self.rows.len().saturating_sub(other.rows.len()),
+            changed_cells,
+        })
+    }
+
+    /// Splits the data rows into tables of at most `n` consecutive rows, each with the same
+    /// header and settings.
+    ///
+    /// Returns the whole table as a single chunk when `n` is 0 or there are no data rows.
+    pub fn chunk_rows(&self, n: usize) -> Vec<MarkdownTable> {
+        if n == 0 || self.rows.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.rows
+            .chunks(n)
+            .enumerate()
+            .map(|(index, rows)| {
+                let start = index * n;
+                let mut table = self.clone();
+                table.rows = rows.to_vec();
+                table.remap_cells(|row, col| {
+                    (start..start + rows.len())
+                        .contains(&row)
+                        .then(|| (row - start, col))
+                });
+
+                table
+            })
+            .collect()
+    }
+
+    /// Splits the data rows into one table per distinct value of `col`, in the order the values
+    /// first appear, each with the same header and settings.
+    pub fn group_by_column(&self, col: usize) -> Result<Vec<(String, MarkdownTable)>> {
+        let cols = self.cols();
+        if col >= cols {
+            return Err(MarkdownTableError::ColumnOutOfRange(col, cols));
+        }
+
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            let key = row.0.get(col).map_or("", String::as_str);
+            match groups.iter_mut().find(|(group_key, _)| group_key == key) {
+                Some((_, indices)) => indices.push(index),
+                None => groups.push((key.to_string(), vec![index])),
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(key, indices)| {
+                let mut table = self.clone();
+                table.rows = indices
+                    .iter()
+                    .map(|&index| self.rows[index].clone())
+                    .collect();
+                table.remap_cells(|row, col| {
+                    let position = indices.iter().position(|&index| index == row)?;
+                    Some((position, col))
+                });
+
+                (key, table)
+            })
+            .collect())
+    }
+
+    /// Builds a table showing the cells that changed from `self` to `other` as `~~old~~ **new**`.
+    ///
+    /// Cells that were filled in show only `**new**`, and cells that were cleared only `~~old~~`.
+    /// Both tables must have the same number of rows and columns.
+    pub fn diff(&self, other: &MarkdownTable) -> Result<MarkdownTable> {
+        let shape = (self.rows.len(), self.cols());
+        let other_shape = (other.rows.len(), other.cols());
+        if shape != other_shape {
+            return Err(MarkdownTableError::ShapeMismatch(shape, other_shape));
+        }
+
+        let rows: Vec<TableRow> = self
+            .rows
+            .iter()
+            .zip(&other.rows)
+            .map(|(old, new)| {
+                TableRow::new(
+                    old.0
+                        .iter()
+                        .zip(&new.0)
+                        .map(|(old, new)| match (old.is_empty(), new.is_empty()) {
+                            _ if old == new => old.clone(),
+                            (true, _) => format!("**{new}**"),
+                            (_, true) => format!("~~{old}~~"),
+                            _ => format!("~~{old}~~ **{new}**"),
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        MarkdownTable::new(self.header.clone(), rows)
+    }
+
+    /// Renders the table inside a fenced code block tagged with `lang`, so it's shown verbatim.
+    ///
+    /// The fence is longer than any run of backticks in the table, so cells can't close it.
+    pub fn to_fenced(&self, lang: Option<&str>) -> String {
+        let rendered = self.to_string();
+        let longest_run = rendered
+            .split(|c| c != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or(0);
+        let fence = "`".repeat((longest_run + 1).max(3));
+
+        format!("{fence}{}\n{rendered}{fence}\n", lang.unwrap_or_default())
+    }
+
+    /// Renders the table as plain text, with columns padded to width and separated by two spaces
+    /// and the header underlined with dashes unless [`MarkdownTable::set_plain_underline`]
+    /// disables it.
+    pub fn to_plain(&self) -> String {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.to_plain();
+        }
+
+        let grid = Grid {
+            super_header: None,
+            footer: None,
+            ..self.plain_grid()
+        };
+        let widths = self.col_lens(&grid);
+
+        let line = |cell: &dyn Fn(usize) -> Cell| {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(col, &len)| {
+                    let cell = cell(col);
+                    let (left, right) = self.alignment(col).padding(cell.width, len);
+                    format!("{}{}{}", " ".repeat(left), cell.text, " ".repeat(right))
+                })
+                .collect();
+
+            format!("{}\n", cells.join("  ").trim_end())
+        };
+
+        let mut plain = String::new();
+        if let Some(ref header) = grid.header {
+            plain.push_str(&line(&|col| header[col].clone()));
+            if self.plain_underline {
+                plain.push_str(&line(&|col| Cell::new("-".repeat(widths[col]))));
+            }
+        }
+        for row in &grid.rows {
+            plain.push_str(&line(&|col| row[col].clone()));
+        }
+
+        plain
+    }
+
+    /// Renders the table as tab-separated values, one line per row.
+    ///
+    /// Tabs and line breaks inside cells are replaced with a space so they can't be mistaken
+    /// for separators.
+    pub fn to_tsv(&self) -> String {
+        let mut tsv = String::new();
+
+        for row in self.header.iter().chain(&self.rows) {
+            let cells: Vec<String> = row
+                .0
+                .iter()
+                .map(|cell| cell.replace(['\t', '\r', '\n'], " "))
+                .collect();
+            tsv.push_str(&cells.join("\t"));
+            tsv.push('\n');
+        }
+
+        tsv
+    }
+
+    /// Renders the data rows as a JSON array, with one object per row keyed by the header.
+    ///
+    /// Tables without a header are rendered as an array of string arrays instead. Cells are
+    /// always JSON strings.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        use serde_json::Value;
+
+        let rows = self.rows.iter().map(|row| match self.header {
+            Some(ref header) => Value::Object(
+                header
+                    .0
+                    .iter()
+                    .zip(&row.0)
+                    .map(|(key, cell)| (key.clone(), Value::String(cell.clone())))
+                    .collect(),
+            ),
+            None => Value::Array(row.0.iter().cloned().map(Value::String).collect()),
+        });
+
+        Value::Array(rows.collect()).to_string()
+    }
+
+    /// Renders the table as an HTML `<table>` element.
+    ///
+    /// Truncated cells keep their full content in a `title` attribute so it shows up on hover.
+    /// When the table has a header, every cell names its column in a `data-col` attribute.
+    pub fn to_html(&self) -> String {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.to_html();
+        }
+
+        let mut html = String::from("<table>\n");
+        if let Some(ref caption) = self.caption {
+            html.push_str(&format!("<caption>{}</caption>\n", escape_html(caption)));
+        }
+
+        let percents: Vec<Option<u8>> = (0..self.cols())
+            .map(|col| self.column_options(col).and_then(|o| o.width_percent))
+            .collect();
+        if percents.iter().any(Option::is_some) {
+            html.push_str("<colgroup>");
+            for percent in percents {
+                match percent {
+                    Some(percent) => html.push_str(&format!("<col style=\"width:{percent}%\">")),
+                    None => html.push_str("<col>"),
+                }
+            }
+            html.push_str("</colgroup>\n");
+        }
+
+        if let Some(ref header) = self.header {
+            html.push_str("<thead>\n");
+            if !self.column_groups.is_empty() {
+                self.push_html_column_groups(&mut html);
+            }
+            self.push_html_row(&mut html, None, header.len(), "th");
+            html.push_str("</thead>\n");
+        }
+
+        html.push_str("<tbody>\n");
+        for (index, row) in self.rows.iter().enumerate() {
+            self.push_html_row(&mut html, Some(index), row.len(), "td");
+        }
+        html.push_str("</tbody>\n</table>\n");
+
+        html
+    }
+
+    /// Number of columns in the data, including hidden columns.
+    pub fn column_count(&self) -> usize {
+        self.cols()
+    }
+
+    fn cols(&self) -> usize {
+        if let Some(ref header) = self.header {
+            header.0.len()
+        } else {
+            self.rows.first().map_or(0, |row| row.0.len())
+        }
+    }
+
+    /// Indices of every row, with `None` standing for the header.
+    fn row_indices(&self) -> impl Iterator<Item = Option<usize>> {
+        let header = self.header.as_ref().map(|_| None);
+
+        header.into_iter().chain((0..self.rows.len()).map(Some))
+    }
+
+    /// The plain grid with pipes escaped so they don't end their cell, and with cell links.
+    fn grid(&self) -> Grid {
+        let mut grid = self.plain_grid();
+        for cell in grid.lines_mut().flatten() {
+            let pipes = cell.text.matches('|').count();
+            if pipes > 0 {
+                cell.text = cell.text.replace('|', "\\|");
+                cell.width += pipes;
+            }
+        }
+
+        for (&(row, col), url) in &self.cell_links {
+            if let Some(cell) = grid.rows.get_mut(row).and_then(|row| row.get_mut(col)) {
+                cell.text = format!("[{}]({})", cell.text, url.replace('|', "\\|"));
+            }
+        }
+
+        grid
+    }
+
+    /// The rendered cells, before pipes are escaped and cell links added.
+    fn plain_grid(&self) -> Grid {
+        let cols = self.cols();
+        let render_row = |row: Option<usize>| {
+            (0..cols)
+                .map(|col| Cell::new(self.rendered_cell(row, col)))
+                .collect()
+        };
+
+        let bold = |cell: &String| {
+            if cell.is_empty() {
+                Cell::new(String::new())
+            } else {
+                Cell::new(format!("**{cell}**"))
+            }
+        };
+
+        let mut grid = Grid {
+            super_header: self
+                .super_header
+                .as_ref()
+                .map(|row| row.0.iter().map(bold).collect()),
+            header: self.header.as_ref().map(|_| render_row(None)),
+            rows: (0..self.rows.len())
+                .map(|row| render_row(Some(row)))
+                .collect(),
+            footer: self.auto_footer.map(|kind| self.footer_row(kind)),
+        };
+
+        if let Some(ref message) = self.empty_message {
+            if self.rows.is_empty() && cols > 0 {
+                let mut row = vec![Cell::new(message.clone())];
+                row.resize_with(cols, || Cell::new(String::new()));
+                grid.rows.push(row);
+            }
+        }
+
+        for col in 0..cols {
+            if self.column_options(col).is_some_and(|o| o.collapse_repeats) {
+                for row in 1..self.rows.len() {
+                    if self.rows[row].0.get(col) == self.rows[row - 1].0.get(col) {
+                        grid.rows[row][col] = Cell::new("\"".to_string());
+                    }
+                }
+            }
+
+            if self.column_options(col).is_some_and(|o| o.decimal_align) {
+                align_decimals(&mut grid.rows, col);
+            }
+        }
+
+        if self.strip_ansi_for_width {
+            for cell in grid.lines_mut().flatten() {
+                cell.width = text_width(&strip_ansi(&cell.text));
+            }
+        }
+
+        grid
+    }
+
+    /// Aggregates of every numeric column, with the first other column labelled with `kind`.
+    /// Row number columns aren't aggregated.
+    ///
+    /// Values are shown with as many decimal places as the column's most precise cell, and means
+    /// with up to two more.
+    fn footer_row(&self, kind: AggregateKind) -> Vec<Cell> {
+        let mut labelled = false;
+
+        (0..self.cols())
+            .map(|col| {
+                match self
+                    .aggregate(col, kind)
+                    .filter(|_| !self.column_options(col).is_some_and(|o| o.row_numbers))
+                {
+                    Some(value) => {
+                        Cell::new(format_aggregate(value, kind, self.decimal_places(col)))
+                    }
+                    None if !labelled => {
+                        labelled = true;
+                        Cell::new(kind.label().to_string())
+                    }
+                    None => Cell::new(String::new()),
+                }
+            })
+            .collect()
+    }
+
+    /// Values of the non-empty data cells in `col`, if there are any and they're all numbers.
+    fn numeric_values(&self, col: usize) -> Option<Vec<f64>> {
+        if col >= self.cols() {
+            return None;
+        }
+
+        let values = self
+            .rows
+            .iter()
+            .map(|row| row.0.get(col).map_or("", |cell| cell.trim()))
+            .filter(|cell| !cell.is_empty())
+            .map(|cell| cell.parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()?;
+
+        (!values.is_empty()).then_some(values)
+    }
+
+    /// Most digits after the decimal point of any data cell in `col`.
+    fn decimal_places(&self, col: usize) -> usize {
+        self.rows
+            .iter()
+            .filter_map(|row| row.0.get(col)?.trim().split_once('.'))
+            .map(|(_, frac)| frac.chars().take_while(char::is_ascii_digit).count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn col_lens(&self, grid: &Grid) -> Vec<usize> {
+        let draws_separator = (grid.header.is_some() && self.emit_separator)
+            || (grid.header.is_none() && self.force_separator)
+            || (grid.rows.len() > 1 && self.row_separators)
+            || grid.footer.is_some();
+        let separator_width = |col: usize| {
+            if draws_separator {
+                self.alignment(col).min_separator_width()
+            } else {
+                0
+            }
+        };
+        let mut widths: Vec<usize> = (0..self.cols())
+            .map(|col| {
+                let content_width = grid.lines().map(|line| line[col].width).max().unwrap_or(0);
+                let min_width = self.column_options(col).and_then(|o| o.min_width);
+
+                content_width
+                    .max(min_width.unwrap_or(0))
+                    .max(separator_width(col))
+            })
+            .collect();
+
+        if self.uniform_width {
+            let widest = widths.iter().copied().max().unwrap_or(0);
+            widths.fill(widest);
+        }
+
+        if let Some(cap) = self.max_render_width {
+            for (col, width) in widths.iter_mut().enumerate() {
+                *width = (*width).min(cap.max(separator_width(col)));
+            }
+        }
+
+        widths
+    }
+
+    /// Spaces to put on either side of the content of `col`, one each by default.
+    fn padding(&self, col: usize) -> (usize, usize) {
+        self.column_options(col)
+            .and_then(|o| o.padding)
+            .unwrap_or((1, 1))
+    }
+
+    /// Characters of a line spent on pipes and padding rather than content.
+    fn line_overhead(&self, cols: usize) -> usize {
+        (0..cols)
+            .map(|col| {
+                let (left, right) = self.padding(col);
+                left + right + 1
+            })
+            .sum::<usize>()
+            + 1
+    }
+
+    fn column_options(&self, col: usize) -> Option<&ColumnOptions> {
+        self.columns.get(&col)
+    }
+
+    fn column_options_mut(&mut self, col: usize) -> &mut ColumnOptions {
+        self.columns.entry(col).or_default()
+    }
+
+    fn alignment(&self, col: usize) -> Alignment {
+        self.column_options(col)
+            .map(|options| options.alignment)
+            .unwrap_or_default()
+    }
+
+    /// Content of a cell in the data row `row`, or in the header when `row` is `None`.
+    ///
+    /// Header cells are replaced by their column's abbreviation, if any.
+    /// Data cells matching the null sentinel are replaced by its display string, and others are
+    /// passed through their column's formatter, if any.
+    fn cell_content(&self, row: Option<usize>, col: usize) -> String {
+        match row {
+            None => match self
+                .column_options(col)
+                .and_then(|o| o.abbreviation.as_ref())
+            {
+                Some(abbreviation) => abbreviation.clone(),
+                None => self
+                    .header
+                    .as_ref()
+                    .and_then(|header| header.0.get(col).cloned())
+                    .unwrap_or_default(),
+            },
+            Some(row) => {
+                let text = self.rows[row].0.get(col).map_or("", String::as_str);
+
+                let content = match (
+                    &self.null_sentinel,
+                    self.column_options(col).and_then(|o| o.formatter.as_ref()),
+                ) {
+                    (Some((sentinel, display_as)), _) if text == sentinel => display_as.clone(),
+                    (_, Some(formatter)) => formatter(text),
+                    _ => text.to_string(),
+                };
+
+                match self.cell_renderer {
+                    Some(ref renderer) => renderer(row, self.column_origin(col), &content),
+                    None => content,
+                }
+            }
+        }
+    }
+
+    fn rendered_cell(&self, row: Option<usize>, col: usize) -> String {
+        let content = self.cell_content(row, col);
+
+        self.display_cell(col, &expand_tabs(&content, self.tab_width))
+    }
+
+    /// Applies the column's display settings to a cell's content.
+    fn display_cell(&self, col: usize, text: &str) -> String {
+        let truncate = if self.strip_ansi_for_width {
+            truncate_ansi
+        } else {
+            truncate
+        };
+
+        match self.max_width(col) {
+            Some(max_width) => truncate(
+                text,
+                max_width,
+                self.column_options(col)
+                    .map_or(TruncatePos::End, |o| o.truncate_pos),
+                &self.ellipsis,
+            ),
+            None => text.to_string(),
+        }
+    }
+
+    /// The narrower of the column's own maximum width and the table-wide cap.
+    fn max_width(&self, col: usize) -> Option<usize> {
+        let column_max = self.column_options(col).and_then(|o| o.max_width);
+
+        match (column_max, self.max_render_width) {
+            (Some(column_max), Some(cap)) => Some(column_max.min(cap)),
+            (column_max, cap) => column_max.or(cap),
+        }
+    }
+
+    fn fmt_table(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        if let Some(table) = self.without_hidden_columns() {
+            return table.fmt_table(f);
+        }
+
+        if self.column_wrap > 0 && self.cols() > self.column_wrap {
+            for (index, cols) in self.wrapped_columns().iter().enumerate() {
+                if index > 0 {
+                    writeln!(f)?;
+                }
+                self.select_columns(cols).fmt_lines(f)?;
+            }
+        } else {
+            self.fmt_lines(f)?;
+        }
+
+        self.fmt_legend(f)
+    }
+
+    /// Writes the lines of the table itself, without wrapping it or adding the legend.
+    fn fmt_lines(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let grid = self.grid();
+        let widths = self.col_lens(&grid);
+
+        self.fmt_header(f, &grid, &widths)?;
+        if grid.header.is_none() && self.force_separator {
+            self.fmt_separator(f, &widths)?;
+        }
+
+        for (index, row) in grid.rows.iter().enumerate() {
+            let repeats_header = grid.header.is_some() && self.repeat_header_every > 0;
+            if index > 0 && repeats_header && index % self.repeat_header_every == 0 {
+                self.fmt_header(f, &grid, &widths)?;
+            } else if index > 0 && self.row_separators {
+                self.fmt_separator(f, &widths)?;
+            }
+
+            self.fmt_line(f, &widths, &|col, _len| row[col].clone())?;
+        }
+
+        if let (true, Some(header)) = (self.footer_header, &grid.header) {
+            if self.emit_separator {
+                self.fmt_separator(f, &widths)?;
+            }
+            self.fmt_line(f, &widths, &|col, _len| header[col].clone())?;
+        }
+
+        if let Some(ref footer) = grid.footer {
+            self.fmt_separator(f, &widths)?;
+            self.fmt_line(f, &widths, &|col, _len| footer[col].clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// Copy of the table without its hidden columns, if it has any.
+    fn without_hidden_columns(&self) -> Option<MarkdownTable> {
+        if !self.columns.values().any(|o| o.hidden) {
+            return None;
+        }
+
+        Some(self.select_columns(&self.visible_columns()))
+    }
+
+    fn visible_columns(&self) -> Vec<usize> {
+        (0..self.cols())
+            .filter(|&col| !self.column_options(col).is_some_and(|o| o.hidden))
+            .collect()
+    }
+
+    /// Columns of each sub-table when wrapping, led by the key column if one is set.
+    fn wrapped_columns(&self) -> Vec<Vec<usize>> {
+        let key = self.wrap_key_column.filter(|&key| key < self.cols());
+        let chunk_len = match key {
+            Some(_) => self.column_wrap.saturating_sub(1).max(1),
+            None => self.column_wrap,
+        };
+
+        let others: Vec<usize> = (0..self.cols()).filter(|&col| Some(col) != key).collect();
+        others
+            .chunks(chunk_len)
+            .map(|chunk| key.into_iter().chain(chunk.iter().copied()).collect())
+            .collect()
+    }
+
+    /// Index of `col` in the table that a copy from `select_columns` was taken from.
+    fn column_origin(&self, col: usize) -> usize {
+        self.column_origins.get(col).copied().unwrap_or(col)
+    }
+
+    /// Moves the links and spans of cells to the cells `map` sends them to, dropping those it
+    /// sends nowhere.
+    fn remap_cells(&mut self, map: impl Fn(usize, usize) -> Option<(usize, usize)>) {
+        self.cell_links = std::mem::take(&mut self.cell_links)
+            .into_iter()
+            .filter_map(|((row, col), url)| Some((map(row, col)?, url)))
+            .collect();
+        self.cell_colspans = std::mem::take(&mut self.cell_colspans)
+            .into_iter()
+            .filter_map(|((row, col), span)| Some((map(row, col)?, span)))
+            .collect();
+    }
+
+    /// Copy of the table keeping only `cols`, in that order.
+    fn select_columns(&self, cols: &[usize]) -> MarkdownTable {
+        let select = |row: &TableRow| {
+            TableRow::new(
+                cols.iter()
+                    .map(|&col| row.0.get(col).cloned().unwrap_or_default())
+                    .collect(),
+            )
+        };
+
+        let mut table = self.clone();
+        table.header = self.header.as_ref().map(select);
+        table.super_header = self.super_header.as_ref().map(select);
+        table.rows = self.rows.iter().map(select).collect();
+        table.columns = cols
+            .iter()
+            .enumerate()
+            .filter_map(|(position, &col)| Some((position, self.column_options(col)?.clone())))
+            .collect();
+        table.remap_cells(|row, col| {
+            let position = cols.iter().position(|&c| c == col)?;
+            Some((row, position))
+        });
+        table.cell_colspans = self
+            .cell_colspans
+            .iter()
+            .filter_map(|(&(row, col), &span)| {
+                let position = cols.iter().position(|&c| c == col)?;
+                let covered = cols.get(position..position + span)?;
+                covered
+                    .iter()
+                    .copied()
+                    .eq(col..col + span)
+                    .then_some(((row, position), span))
+            })
+            .collect();
+        table.column_groups = self
+            .column_groups
+            .iter()
+            .filter_map(|(start, end, label)| {
+                let positions: Vec<usize> = (0..cols.len())
+                    .filter(|&position| (*start..*end).contains(&cols[position]))
+                    .collect();
+                let (&first, &last) = (positions.first()?, positions.last()?);
+                (last - first + 1 == positions.len()).then(|| (first, last + 1, label.clone()))
+            })
+            .collect();
+        table.column_groups.sort_by_key(|&(start, _, _)| start);
+        table.column_origins = cols.iter().map(|&col| self.column_origin(col)).collect();
+        table.wrap_key_column = self
+            .wrap_key_column
+            .and_then(|key| cols.iter().position(|&col| col == key));
+
+        table
+    }
+
+    fn fmt_header(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        grid: &Grid,
+        widths: &[usize],
+    ) -> std::fmt::Result {
+        if let Some(ref super_header) = grid.super_header {
+            self.fmt_line(f, widths, &|col, _len| super_header[col].clone())?;
+        }
+
+        if let Some(ref header) = grid.header {
+            self.fmt_line(f, widths, &|col, _len| header[col].clone())?;
+            if self.emit_separator {
+                self.fmt_separator(f, widths)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fmt_separator(&self, f: &mut dyn std::fmt::Write, widths: &[usize]) -> std::fmt::Result {
+        self.fmt_line(f, widths, &|col, len| {
+            Cell::new(self.alignment(col).separator(len))
+        })
+    }
+
+    /// Lists the full names of abbreviated headers below the table.
+    fn fmt_legend(&self, f: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let Some(ref header) = self.header else {
+            return Ok(());
+        };
+
+        let mut abbreviations = self
+            .columns
+            .iter()
+            .filter_map(|(&col, options)| {
+                Some((options.abbreviation.as_ref()?, header.0.get(col)?))
+            })
+            .peekable();
+
+        if abbreviations.peek().is_some() {
+            writeln!(f)?;
+            for (abbreviation, name) in abbreviations {
+                writeln!(f, "- {abbreviation} = {name}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fmt_line(
+        &self,
+        f: &mut dyn std::fmt::Write,
+        widths: &[usize],
+        pred: &dyn Fn(usize, usize) -> Cell,
+    ) -> std::fmt::Result {
+        for (col, &len) in widths.iter().enumerate() {
+            let cell = pred(col, len);
+            let (left, mut right) = self.alignment(col).padding(cell.width, len);
+
+            if self.trim_trailing && col + 1 == widths.len() {
+                right = 0;
+            }
+
+            let (pad_left, pad_right) = self.padding(col);
+            let fill = if self.nbsp_padding { "\u{00A0}" } else { " " };
+            write!(
+                f,
+                "|{}{}{}{}{}",
+                " ".repeat(pad_left),
+                fill.repeat(left),
+                cell.text,
+                fill.repeat(right),
+                " ".repeat(pad_right)
+            )?;
+        }
+        writeln!(f, "|")
+    }
+
+    /// A single line as `fmt_line` writes it, without the line break.
+    fn line_string(&self, widths: &[usize], pred: &dyn Fn(usize, usize) -> Cell) -> String {
+        let mut line = String::new();
+        self.fmt_line(&mut line, widths, pred)
+            .expect("writing to a String can't fail");
+        line.pop();
+
+        line
+    }
+
+    fn push_html_column_groups(&self, html: &mut String) {
+        html.push_str("<tr>");
+
+        let mut col = 0;
+        for (start, end, label) in &self.column_groups {
+            html.push_str(&"<th></th>".repeat(start - col));
+            html.push_str(&format!(
+                "<th colspan=\"{}\">{}</th>",
+                end - start,
+                escape_html(label)
+            ));
+            col = *end;
+        }
+        html.push_str(&"<th></th>".repeat(self.cols().saturating_sub(col)));
+
+        html.push_str("</tr>\n");
+    }
+
+    fn push_html_row(&self, html: &mut String, row: Option<usize>, cols: usize, tag: &str) {
+        html.push_str("<tr>");
+        let mut col = 0;
+        while col < cols {
+            let content = self.cell_content(row, col);
+            let text = self.display_cell(col, &content);
+            let span = row
+                .and_then(|row| self.cell_colspans.get(&(row, col)))
+                .map_or(1, |&span| span.min(cols - col));
+
+            html.push('<');
+            html.push_str(tag);
+            if let Some(name) = self.header.as_ref().and_then(|header| header.0.get(col)) {
+                html.push_str(&format!(" data-col=\"{}\"", escape_html(name)));
+            }
+            if span > 1 {
+                html.push_str(&format!(" colspan=\"{span}\""));
+            }
+            html.push('>');
+
+            if text == content {
+                html.push_str(&escape_html(&content));
+            } else {
+                html.push_str(&format!(
+                    "<span title=\"{}\">{}</span>",
+                    escape_html(&content),
+                    escape_html(&text)
+                ));
+            }
+
+            html.push_str(&format!("</{tag}>"));
+            col += span;
+        }
+        html.push_str("</tr>\n");
+    }
+
+    fn validate_row_length(header: &TableRow, new_row: &TableRow) -> Result<()> {
+        let header_len = header.0.len();
+        let new_len = new_row.0.len();
+
+        if header_len != new_len {
+            Err(MarkdownTableError::InvalidRowLength(header_len, new_len))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Step-by-step construction of a [`MarkdownTable`], started with [`MarkdownTable::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownTableBuilder {
+    header: Option<TableRow>,
+    rows: Vec<TableRow>,
+    unchecked: bool,
+    max_columns: Option<usize>,
+}
+
+impl MarkdownTableBuilder {
+    pub fn header(mut self, header: impl Into<TableRow>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    pub fn row(mut self, row: impl Into<TableRow>) -> Self {
+        self.rows.push(row.into());
+        self
+    }
+
+    pub fn rows<I, T>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<TableRow>,
+    {
+        self.rows.extend(rows.into_iter().map(Into::into));
+        self
+    }
+
+    /// Skips checking that every row has the same length as the header when building.
+    ///
+    /// Only use this for data that's known to be rectangular. Nothing stops malformed tables
+    /// from being built this way; missing cells render as empty.
+    pub fn unchecked(mut self) -> Self {
+        self.unchecked = true;
+        self
+    }
+
+    /// Fails the build with [`MarkdownTableError::TooManyColumns`] when the table has more than
+    /// `max` columns.
+    pub fn max_columns(mut self, max: usize) -> Self {
+        self.max_columns = Some(max);
+        self
+    }
+
+    pub fn build(self) -> Result<MarkdownTable> {
+        if let Some(max) = self.max_columns {
+            let cols = self
+                .header
+                .as_ref()
+                .or_else(|| self.rows.first())
+                .map_or(0, TableRow::len);
+            if cols > max {
+                return Err(MarkdownTableError::TooManyColumns(max, cols));
+            }
+        }
+
+        if !self.unchecked {
+            return MarkdownTable::new(self.header, self.rows);
+        }
+
+        if self.header.is_none() && self.rows.is_empty() {
+            return Err(MarkdownTableError::NoRowsSpecified);
+        }
+
+        Ok(MarkdownTable::from_parts(self.header, self.rows))
+    }
+}
+
+/// Number of characters `text` takes up when rendered.
+fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Formats an aggregate of a column whose cells have up to `decimals` decimal places.
+fn format_aggregate(value: f64, kind: AggregateKind, decimals: usize) -> String {
+    match kind {
+        AggregateKind::Count => value.to_string(),
+        AggregateKind::Mean => {
+            let mean = format!("{value:.*}", decimals + 2);
+            mean.trim_end_matches('0').trim_end_matches('.').to_string()
+        }
+        AggregateKind::Sum | AggregateKind::Max | AggregateKind::Min => {
+            format!("{value:.decimals$}")
+        }
+    }
+}
+
+/// Orders two cells numerically when both are numbers, and as text otherwise, with numbers
+/// before text.
+fn compare_cells(a: &str, b: &str) -> std::cmp::Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+        (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Removes ANSI CSI escape sequences like `\x1b[31m` from `text`.
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// Replaces the tabs in `text` with spaces up to the next tab stop every `tab_width` characters.
+fn expand_tabs(text: &str, tab_width: usize) -> String {
+    if tab_width == 0 || !text.contains('\t') {
+        return text.to_string();
+    }
+
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        if c == '\t' {
+            let spaces = tab_width - column % tab_width;
+            expanded.push_str(&" ".repeat(spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+
+    expanded
+}
+
+/// Shortens `text` to at most `width` characters, with `ellipsis` at `pos` when cut off.
+///
+/// Only the ellipsis is kept when it doesn't fit in `width` by itself, cut down to `width` if
+/// it's wider.
+fn truncate(text: &str, width: usize, pos: TruncatePos, ellipsis: &str) -> String {
+    let len = text_width(text);
+    if len <= width {
+        return text.to_string();
+    }
+
+    let ellipsis = fitted_ellipsis(ellipsis, width);
+    let (front, back) = truncated_ends(width, pos, ellipsis);
+
+    let mut truncated: String = text.chars().take(front).collect();
+    truncated.push_str(ellipsis);
+    truncated.extend(text.chars().skip(len - back));
+    truncated
+}
+
+/// Like [`truncate`], but only counts characters outside ANSI escape sequences and keeps every
+/// sequence, so styles are still reset after the cut.
+fn truncate_ansi(text: &str, width: usize, pos: TruncatePos, ellipsis: &str) -> String {
+    let len = text_width(&strip_ansi(text));
+    if len <= width {
+        return text.to_string();
+    }
+
+    let ellipsis = fitted_ellipsis(ellipsis, width);
+    let (front, back) = truncated_ends(width, pos, ellipsis);
+
+    let mut truncated = String::with_capacity(text.len());
+    let mut index = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            truncated.push(c);
+            truncated.extend(chars.next());
+            for c in chars.by_ref() {
+                truncated.push(c);
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        if index == front {
+            truncated.push_str(ellipsis);
+        }
+        if index < front || index >= len - back {
+            truncated.push(c);
+        }
+        index += 1;
+    }
+
+    truncated
+}
+
+/// The first `width` characters of `ellipsis`.
+fn fitted_ellipsis(ellipsis: &str, width: usize) -> &str {
+    ellipsis
+        .char_indices()
+        .nth(width)
+        .map_or(ellipsis, |(end, _)| &ellipsis[..end])
+}
+
+/// Characters to keep from the start and end of text truncated to `width`.
+fn truncated_ends(width: usize, pos: TruncatePos, ellipsis: &str) -> (usize, usize) {
+    let kept = width.saturating_sub(text_width(ellipsis));
+
+    match pos {
+        TruncatePos::Start => (0, kept),
+        TruncatePos::Middle => (kept - kept / 2, kept / 2),
+        TruncatePos::End => (kept, 0),
+    }
+}
+
+/// Pads the numeric cells of `col` so that their decimal points line up.
+fn align_decimals(rows: &mut [Vec<Cell>], col: usize) {
+    let parts: Vec<Option<(String, Option<String>)>> = rows
+        .iter()
+        .map(|row| split_decimal(&row[col].text))
+        .collect();
+
+    let int_width = parts
+        .iter()
+        .flatten()
+        .map(|(int, _)| text_width(int))
+        .max()
+        .unwrap_or(0);
+    let frac_width = parts
+        .iter()
+        .flatten()
+        .filter_map(|(_, frac)| frac.as_deref())
+        .map(text_width)
+        .max();
+
+    for (row, parts) in rows.iter_mut().zip(parts) {
+        if let Some((int, frac)) = parts {
+            let mut cell = format!("{int:>int_width$}");
+            match (frac, frac_width) {
+                (Some(frac), Some(width)) => cell.push_str(&format!(".{frac:<width$}")),
+                (None, Some(width)) => cell.push_str(&" ".repeat(width + 1)),
+                _ => {}
+            }
+            row[col] = Cell::new(cell);
+        }
+    }
+}
+
+/// Splits a plain decimal number like `-12.5` into its integer and fractional parts.
+fn split_decimal(cell: &str) -> Option<(String, Option<String>)> {
+    let cell = cell.trim();
+    let is_decimal = cell.parse::<f64>().is_ok()
+        && cell
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.'));
+
+    if !is_decimal {
+        return None;
+    }
+
+    Some(match cell.split_once('.') {
+        Some((int, frac)) => (int.to_string(), Some(frac.to_string())),
+        None => (cell.to_string(), None),
+    })
+}
+
+/// Wraps `text` in a code span longer than any run of backticks inside it.
+fn code_span(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    let padding = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+
+    format!("{fence}{padding}{text}{padding}{fence}")
+}
+
+/// Groups the integer digits of a plain decimal number in threes with `separator`.
+fn group_digits(cell: &str, separator: char) -> Option<String> {
+    let (int, frac) = split_decimal(cell)?;
+    let (sign, digits) = int.split_at(int.len() - int.trim_start_matches(['+', '-']).len());
+
+    let mut grouped = sign.to_string();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    if let Some(frac) = frac {
+        grouped.push('.');
+        grouped.push_str(&frac);
+    }
+
+    Some(grouped)
+}
+
+/// Splits a markdown table line into its trimmed cells, unescaping `\\|`.
+fn split_markdown_row(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                cells.last_mut().unwrap().push('|');
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(c),
+        }
+    }
+
+    cells.iter().map(|cell| cell.trim().to_string()).collect()
+}
+
+fn is_separator_row(cells: &[String]) -> bool {
+    cells
+        .iter()
+        .all(|cell| !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':'))
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableRow(Vec<String>);
+
+/// Conversion from a data row back into a value, the inverse of `Into<TableRow>`.
+pub trait FromTableRow: Sized {
+    type Error;
+
+    fn from_table_row(row: &TableRow) -> std::result::Result<Self, Self::Error>;
+}
+
+impl TableRow {
+    pub fn new(data: Vec<String>) -> Self {
+        Self(data)
+    }
+
+    /// Number of cells in the row.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the row has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Content of the cell at `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).map(|cell| cell.as_str())
+    }
+
+    /// Replaces the content of the cell at `index`.
+    pub fn set(&mut self, index: usize, value: impl Into<String>) -> Result<()> {
+        let len = self.0.len();
+        let cell = self
+            .0
+            .get_mut(index)
+            .ok_or(MarkdownTableError::ColumnOutOfRange(index, len))?;
+        *cell = value.into();
+
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Display, const N: usize> From<&[T; N]> for TableRow {
+    fn from(value: &[T; N]) -> Self {
+        Self::new(value.iter().map(|v| v.to_string()).collect())
+    }
+}
+
+impl<T: std::fmt::Display> From<&[T]> for TableRow {
+    fn from(value: &[T]) -> Self {
+        Self::new(value.iter().map(|v| v.to_string()).collect())
+    }
+}
+
+impl<T: std::fmt::Display> From<Vec<T>> for TableRow {
+    fn from(value: Vec<T>) -> Self {
+        Self::new(value.iter().map(|v| v.to_string()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct DummyRow {
+        data: Vec<String>,
+    }
+
+    impl From<DummyRow> for TableRow {
+        fn from(val: DummyRow) -> Self {
+            TableRow(val.data)
+        }
+    }
+
+    impl From<&DummyRow> for TableRow {
+        fn from(val: &DummyRow) -> Self {
+            TableRow(val.data.clone())
+        }
+    }
+
+    #[test]
+    fn adding_row() {
+        let dd = DummyRow {
+            data: vec!["a".to_string(), "b".to_string()],
+        };
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), vec![dd]).unwrap();
+
+        let res = mt.add_row(DummyRow {
+            data: vec!["c".to_string(), "d".to_string()],
+        });
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn wrong_row_size() {
+        let dd = DummyRow {
+            data: vec!["a".to_string(), "b".to_string()],
+        };
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), vec![dd]).unwrap();
+
+        let res = mt.add_row(DummyRow {
+            data: vec!["d".to_string()],
+        });
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn to_string() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c".to_string(), "d".to_string()],
+            },
+            DummyRow {
+                data: vec!["e".to_string(), "fg".to_string()],
+            },
+        ];
+
+        let mt = MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+
+        println!("{}", mt);
+    }
+
+    #[test]
+    fn extending_from_iterator() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Hoi".to_string(), "Bye".to_string()]),
+            vec![DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            }],
+        )
+        .unwrap();
+
+        let res = mt.extend_from(["c", "e"].iter().map(|v| DummyRow {
+            data: vec![v.to_string(), v.to_uppercase()],
+        }));
+        assert!(res.is_ok());
+        assert_eq!(mt.rows.len(), 3);
+
+        let res = mt.extend_from(vec![
+            DummyRow {
+                data: vec!["f".to_string(), "g".to_string()],
+            },
+            DummyRow {
+                data: vec!["h".to_string()],
+            },
+        ]);
+        assert!(res.is_err());
+        assert_eq!(mt.rows.len(), 3);
+    }
+
+    #[test]
+    fn trim_trailing() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c".to_string(), "defg".to_string()],
+            },
+        ];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+        assert!(mt.to_string().contains("b    |"));
+
+        mt.set_trim_trailing(true);
+        let rendered = mt.to_string();
+        assert!(rendered.lines().all(|line| !line.ends_with("  |")));
+        assert_eq!(rendered.lines().nth(2), Some("| a   | b |"));
+    }
+
+    #[test]
+    fn swapping_rows() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c".to_string(), "d".to_string()],
+            },
+        ];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+
+        assert!(mt.swap_rows(0, 1).is_ok());
+        assert_eq!(
+            mt.to_string(),
+            "| Hoi | Bye |\n| --- | --- |\n| c   | d   |\n| a   | b   |\n"
+        );
+        assert!(mt.swap_rows(0, 2).is_err());
+    }
+
+    #[test]
+    fn max_column_width() {
+        let dd = vec![DummyRow {
+            data: vec!["a".to_string(), "Hello, world!".to_string()],
+        }];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+        mt.set_max_column_width(1, 6);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Hoi | Bye    |\n| --- | ------ |\n| a   | Hello… |\n"
+        );
+    }
+
+    #[test]
+    fn out_of_range_column_options() {
+        let mut mt = MarkdownTable::new(Some(vec!["Hoi"]), vec![vec!["a"]]).unwrap();
+        mt.set_alignment(usize::MAX, Alignment::Right);
+        mt.set_min_column_width(1 << 40, 1);
+
+        assert_eq!(mt.to_string(), "| Hoi |\n| --- |\n| a   |\n");
+        assert!(matches!(
+            mt.try_render(),
+            Err(MarkdownTableError::ColumnOutOfRange(_, 1))
+        ));
+    }
+
+    #[test]
+    fn html_truncated_title() {
+        let dd = vec![DummyRow {
+            data: vec!["a".to_string(), "Fish & \"chips\"".to_string()],
+        }];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+        let html = mt.to_html();
+        assert!(html.contains("<td data-col=\"Bye\">Fish &amp; &quot;chips&quot;</td>"));
+        assert!(!html.contains("title="));
+
+        mt.set_max_column_width(1, 5);
+        let html = mt.to_html();
+        assert!(html.contains(
+            "<td data-col=\"Bye\"><span title=\"Fish &amp; &quot;chips&quot;\">Fish…</span></td>"
+        ));
+    }
+
+    #[test]
+    fn mutating_rows() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c".to_string(), "d".to_string()],
+            },
+        ];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+
+        for row in mt.rows_mut() {
+            let upper = row.get(0).unwrap().to_uppercase();
+            row.set(0, upper).unwrap();
+        }
+
+        let firsts: Vec<&str> = mt.rows().map(|row| row.get(0).unwrap()).collect();
+        assert_eq!(firsts, vec!["A", "C"]);
+        assert!(mt.rows_mut().next().unwrap().set(2, "x").is_err());
+    }
+
+    #[test]
+    fn alignment() {
+        let dd = vec![DummyRow {
+            data: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        }];
+
+        let mut mt = MarkdownTable::new(
+            Some(vec![
+                "Hoi".to_string(),
+                "Bye".to_string(),
+                "Ciao".to_string(),
+            ]),
+            dd,
+        )
+        .unwrap();
+        mt.set_alignment(0, Alignment::Left);
+        mt.set_alignment(1, Alignment::Right);
+        mt.set_alignment(2, Alignment::Center);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Hoi | Bye | Ciao |\n| :-- | --: | :--: |\n| a   |   b |  c   |\n"
+        );
+    }
+
+    #[test]
+    fn parsing() {
+        let input = "| Name  | Age |\n| ----- | --: |\n| Alice |  28 |\n| Bob   |   7 |\n";
+
+        let mt = MarkdownTable::parse(input).unwrap();
+        assert_eq!(mt.rows.len(), 2);
+        assert_eq!(mt.alignment(1), Alignment::Right);
+        assert_eq!(mt.to_string(), input);
+
+        let headerless = MarkdownTable::parse("| a | b \\| c |\n| d | e |").unwrap();
+        assert!(headerless.header.is_none());
+        assert_eq!(headerless.rows[0].get(1), Some("b | c"));
+        assert_eq!(headerless.to_string(), "| a | b \\| c |\n| d | e      |\n");
+        assert_eq!(headerless.rendered_width(), "| a | b \\| c |".len());
+        let reparsed = MarkdownTable::parse(&headerless.to_string()).unwrap();
+        assert_eq!(reparsed.rows[0].get(1), Some("b | c"));
+        assert_eq!(headerless.to_plain(), "a  b | c\nd  e\n");
+
+        assert!(MarkdownTable::parse("| a | b |\n| -:- | --- |").is_err());
+        assert!(MarkdownTable::parse("| a | b |\n| --- |").is_err());
+        assert!(MarkdownTable::parse("").is_err());
+    }
+
+    #[test]
+    fn row_numbers() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c".to_string(), "d".to_string()],
+            },
+            DummyRow {
+                data: vec!["e".to_string(), "f".to_string()],
+            },
+        ];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+        mt.set_alignment(1, Alignment::Right);
+
+        let numbered = mt.with_row_numbers("#");
+        assert_eq!(
+            numbered.to_string(),
+            "| # | Hoi | Bye |\n| - | --- | --: |\n| 1 | a   |   b |\n| 2 | c   |   d |\n| 3 | e   |   f |\n"
+        );
+        assert_eq!(mt.rows[0].len(), 2);
+
+        assert!(mt.set_cell_link(0, 0, "http://x").is_ok());
+        assert!(mt
+            .with_row_numbers("#")
+            .to_string()
+            .contains("| 1 | [a](http://x)   |   b |"));
+    }
+
+    #[test]
+    fn rendered_width() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["ä".to_string(), "Hello".to_string()],
+            },
+        ];
+
+        let mt = MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
+        let rendered = mt.to_string();
+
+        assert_eq!(
+            mt.rendered_width(),
+            rendered.lines().next().unwrap().chars().count()
+        );
+    }
+
+    #[test]
+    fn checkbox_column() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["Write tests".to_string(), "true".to_string()],
+            },
+            DummyRow {
+                data: vec!["Ship it".to_string(), "0".to_string()],
+            },
+            DummyRow {
+                data: vec!["Celebrate".to_string(), "maybe".to_string()],
+            },
+        ];
+
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Task".to_string(), "Done".to_string()]), dd).unwrap();
+        mt.set_checkbox_column(1);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Task        | Done  |\n| ----------- | ----- |\n| Write tests | [x]   |\n| Ship it     | [ ]   |\n| Celebrate   | maybe |\n"
+        );
+        assert_eq!(mt.rows[0].get(1), Some("true"));
+    }
+
+    #[test]
+    fn try_from_grid() {
+        let grid = vec![
+            vec!["Hoi".to_string(), "Bye".to_string()],
+            vec!["a".to_string(), "b".to_string()],
+        ];
+
+        let mt = MarkdownTable::try_from(grid).unwrap();
+        assert_eq!(
+            mt.to_string(),
+            "| Hoi | Bye |\n| --- | --- |\n| a   | b   |\n"
+        );
+
+        let ragged = vec![
+            vec!["Hoi".to_string(), "Bye".to_string()],
+            vec!["a".to_string()],
+        ];
+        assert!(matches!(
+            MarkdownTable::try_from(ragged),
+            Err(MarkdownTableError::InvalidRowLength(2, 1))
+        ));
+        assert!(matches!(
+            MarkdownTable::try_from(Vec::new()),
+            Err(MarkdownTableError::NoRowsSpecified)
+        ));
+    }
+
+    #[test]
+    fn repeating_header() {
+        let dd: Vec<Vec<String>> = (1..=5).map(|i| vec![i.to_string()]).collect();
+
+        let mut mt = MarkdownTable::new(Some(vec!["N".to_string()]), dd).unwrap();
+        mt.set_repeat_header_every(2);
+
+        assert_eq!(
+            mt.to_string(),
+            "| N |\n| - |\n| 1 |\n| 2 |\n| N |\n| - |\n| 3 |\n| 4 |\n| N |\n| - |\n| 5 |\n"
+        );
+        let mut headerless =
+            MarkdownTable::headerless(vec![vec!["1"], vec!["2"], vec!["3"]]).unwrap();
+        assert!(headerless.set_super_header(vec!["G"]).is_ok());
+        headerless.set_repeat_header_every(2);
+        assert_eq!(
+            headerless.to_string(),
+            "| **G** |\n| 1     |\n| 2     |\n| 3     |\n"
+        );
+    }
+
+    #[test]
+    fn diffing() {
+        let old = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Dennis", "22"]],
+        )
+        .unwrap();
+        let new = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "29"], vec!["Dennis", "22"]],
+        )
+        .unwrap();
+
+        let diff = old.diff(&new).unwrap();
+        assert_eq!(
+            diff.rows[0],
+            TableRow::from(vec!["Jessica", "~~28~~ **29**"])
+        );
+        assert_eq!(diff.rows[1], TableRow::from(vec!["Dennis", "22"]));
+
+        let blanks = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", ""], vec!["", "22"]],
+        )
+        .unwrap();
+        let diff = old.diff(&blanks).unwrap();
+        assert_eq!(diff.rows[0], TableRow::from(vec!["Jessica", "~~28~~"]));
+        let diff = blanks.diff(&old).unwrap();
+        assert_eq!(diff.rows[0], TableRow::from(vec!["Jessica", "**28**"]));
+        assert_eq!(diff.rows[1], TableRow::from(vec!["**Dennis**", "22"]));
+
+        let short =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(matches!(
+            old.diff(&short),
+            Err(MarkdownTableError::ShapeMismatch((2, 2), (1, 2)))
+        ));
+    }
+
+    #[test]
+    fn reading_header() {
+        let mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+
+        let header = mt.header().unwrap();
+        assert_eq!(header.len(), 2);
+        assert_eq!(header.get(1), Some("Age"));
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(headerless.header().is_none());
+    }
+
+    #[test]
+    fn decimal_alignment() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Amount"]),
+            vec![
+                vec!["1.5"],
+                vec!["12.25"],
+                vec!["100.0"],
+                vec!["7"],
+                vec!["n/a"],
+            ],
+        )
+        .unwrap();
+        mt.set_decimal_align(0);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Amount |\n| -----: |\n|   1.5  |\n|  12.25 |\n| 100.0  |\n|   7    |\n|    n/a |\n"
+        );
+    }
+
+    #[test]
+    fn html_data_col() {
+        let mt =
+            MarkdownTable::new(Some(vec!["Name", "<Age>"]), vec![vec!["Jessica", "28"]]).unwrap();
+        let html = mt.to_html();
+        assert!(html.contains(
+            "<tr><th data-col=\"Name\">Name</th><th data-col=\"&lt;Age&gt;\">&lt;Age&gt;</th></tr>"
+        ));
+        assert!(html.contains(
+            "<tr><td data-col=\"Name\">Jessica</td><td data-col=\"&lt;Age&gt;\">28</td></tr>"
+        ));
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(headerless
+            .to_html()
+            .contains("<tr><td>Jessica</td><td>28</td></tr>"));
+
+        let ragged = MarkdownTable::builder()
+            .header(vec!["Name"])
+            .row(vec!["Jessica", "28"])
+            .unchecked()
+            .build()
+            .unwrap();
+        assert!(ragged
+            .to_html()
+            .contains("<tr><td data-col=\"Name\">Jessica</td><td>28</td></tr>"));
+    }
+
+    #[test]
+    fn renaming_column_header() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+
+        assert!(mt.set_column_header(1, "Years").is_ok());
+        assert!(mt.to_string().starts_with("| Name    | Years |\n"));
+        assert!(matches!(
+            mt.set_column_header(2, "Oops"),
+            Err(MarkdownTableError::ColumnOutOfRange(2, 2))
+        ));
+
+        let mut headerless =
+            MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(matches!(
+            headerless.set_column_header(0, "Name"),
+            Err(MarkdownTableError::NoHeader)
+        ));
+    }
+
+    #[test]
+    fn rendering_into_buffer() {
+        let mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        let mut buf = String::new();
+
+        mt.render_into(&mut buf);
+        assert_eq!(buf, mt.to_string());
+
+        buf.clear();
+        mt.render_into(&mut buf);
+        assert_eq!(buf, mt.to_string());
+    }
+
+    #[test]
+    fn looking_up_column_index() {
+        let mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        assert_eq!(mt.column_index("Age"), Some(1));
+        assert_eq!(mt.column_index("Height"), None);
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Name", "Age"]]).unwrap();
+        assert_eq!(headerless.column_index("Age"), None);
+    }
+
+    #[test]
+    fn cell_access_by_name() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+
+        assert_eq!(mt.get_by_name(0, "Name"), Some("Jessica"));
+        assert_eq!(mt.get_by_name(0, "Height"), None);
+        assert_eq!(mt.get_by_name(1, "Name"), None);
+
+        assert!(mt.set_by_name(0, "Age", "29").is_ok());
+        assert_eq!(mt.get_cell(0, 1), Some("29"));
+        assert!(matches!(
+            mt.set_by_name(0, "Height", "1.70"),
+            Err(MarkdownTableError::UnknownColumn(_))
+        ));
+        assert!(matches!(
+            mt.set_by_name(1, "Age", "30"),
+            Err(MarkdownTableError::RowOutOfRange(1, 1))
+        ));
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert_eq!(headerless.get_by_name(0, "Name"), None);
+    }
+
+    #[test]
+    fn validating() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(mt.validate().is_ok());
+
+        mt.rows.push(TableRow::from(vec!["Dennis"]));
+        mt.rows.push(TableRow::from(vec!["Bob", "7", "extra"]));
+
+        let errors = mt.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            MarkdownTableError::InvalidRowLength(2, 1)
+        ));
+        assert!(matches!(
+            errors[1],
+            MarkdownTableError::InvalidRowLength(2, 3)
+        ));
+    }
+
+    #[test]
+    fn header_only() {
+        let mut mt = MarkdownTable::new(Some(vec!["Name", "Age"]), Vec::<TableRow>::new()).unwrap();
+        mt.set_alignment(1, Alignment::Right);
+
+        assert_eq!(mt.to_string(), "| Name | Age |\n| ---- | --: |\n");
+
+        assert!(matches!(
+            MarkdownTable::new(None::<TableRow>, Vec::<TableRow>::new()),
+            Err(MarkdownTableError::NoRowsSpecified)
+        ));
+    }
+
+    #[test]
+    fn from_hash_map() {
+        let map: std::collections::HashMap<&str, u32> =
+            [("Jessica", 28), ("Dennis", 22), ("Bob", 7)]
+                .into_iter()
+                .collect();
+
+        let mt = MarkdownTable::try_from(map).unwrap();
+        assert_eq!(
+            mt.to_string(),
+            "| Bob     | 7  |\n| Dennis  | 22 |\n| Jessica | 28 |\n"
+        );
+
+        let empty = MarkdownTable::try_from(std::collections::HashMap::<String, String>::new());
+        assert!(matches!(empty, Err(MarkdownTableError::NoRowsSpecified)));
+    }
+
+    #[test]
+    fn aligning_widths() {
+        let mut first =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        let mut others =
+            vec![MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Bob", "107"]]).unwrap()];
+
+        assert!(first.align_widths_with(&mut others).is_ok());
+        assert_eq!(
+            first.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n"
+        );
+        assert_eq!(
+            others[0].to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Bob     | 107 |\n"
+        );
+
+        let mut mismatched =
+            vec![MarkdownTable::new(Some(vec!["Name"]), vec![vec!["Bob"]]).unwrap()];
+        assert!(matches!(
+            first.align_widths_with(&mut mismatched),
+            Err(MarkdownTableError::ColumnCountMismatch(2, 1))
+        ));
+    }
+
+    #[test]
+    fn resetting_column_constraints() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        let full = mt.to_string();
+
+        mt.set_max_column_width(0, 4);
+        mt.set_min_column_width(1, 6);
+        assert_ne!(mt.to_string(), full);
+
+        mt.reset_column_constraints();
+        assert_eq!(mt.to_string(), full);
+    }
+
+    #[test]
+    fn alignment_spec() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Hoi", "Bye", "Ciao"]), vec![vec!["a", "b", "c"]])
+                .unwrap();
+
+        assert!(mt.set_alignment_spec("lrc").is_ok());
+        assert_eq!(mt.to_string().lines().nth(1), Some("| :-- | --: | :--: |"));
+
+        assert!(mt.set_alignment_spec("> < -").is_ok());
+        assert_eq!(mt.to_string().lines().nth(1), Some("| --: | :-- | ---- |"));
+
+        assert!(matches!(
+            mt.set_alignment_spec("lr"),
+            Err(MarkdownTableError::ColumnCountMismatch(3, 2))
+        ));
+        assert!(matches!(
+            mt.set_alignment_spec("lrx"),
+            Err(MarkdownTableError::InvalidAlignment('x'))
+        ));
+    }
+
+    #[test]
+    fn omitting_separator() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        mt.set_emit_separator(false);
+
+        let rendered = mt.to_string();
+        assert!(!rendered.contains("---"));
+        assert_eq!(rendered, "| Name    | Age |\n| Jessica | 28  |\n");
+    }
+
+    #[test]
+    fn counting_cells() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![
+                vec!["Jessica", "28"],
+                vec!["Dennis", "22"],
+                vec!["Bob", "7"],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(mt.cell_count(false), 6);
+        assert_eq!(mt.cell_count(true), 8);
+
+        let content: usize = ["Name", "Age", "Jessica", "28", "Dennis", "22", "Bob", "7"]
+            .iter()
+            .map(|cell| cell.len())
+            .sum();
+        assert!(mt.estimated_bytes() > content);
+    }
+
+    #[test]
+    fn super_header() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age", "City"]),
+            vec![vec!["Jessica", "28", "Utrecht"]],
+        )
+        .unwrap();
+
+        assert!(mt.set_super_header(vec!["Person", "", "Location"]).is_ok());
+        assert_eq!(
+            mt.to_string(),
+            "| **Person** |     | **Location** |\n| Name       | Age | City         |\n| ---------- | --- | ------------ |\n| Jessica    | 28  | Utrecht      |\n"
+        );
+        assert!(matches!(
+            mt.set_super_header(vec!["Person"]),
+            Err(MarkdownTableError::InvalidRowLength(3, 1))
+        ));
+    }
+
+    #[test]
+    fn null_sentinel() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Dennis", "\u{0}"]],
+        )
+        .unwrap();
+        mt.set_null_sentinel("\u{0}", "—");
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n| Dennis  | —   |\n"
+        );
+        assert_eq!(mt.get_cell(1, 1), Some("\u{0}"));
+    }
+
+    #[test]
+    fn fallible_iterator() {
+        #[derive(Debug)]
+        enum LoadError {
+            Parse(String),
+            Table(MarkdownTableError),
+        }
+
+        impl From<MarkdownTableError> for LoadError {
+            fn from(err: MarkdownTableError) -> Self {
+                LoadError::Table(err)
+            }
+        }
+
+        let parse = |line: &str| -> std::result::Result<Vec<String>, LoadError> {
+            match line.split_once(',') {
+                Some((name, age)) => Ok(vec![name.to_string(), age.to_string()]),
+                None => Err(LoadError::Parse(line.to_string())),
+            }
+        };
+
+        let mt = MarkdownTable::try_from_iter(
+            Some(vec!["Name", "Age"]),
+            ["Jessica,28", "Dennis,22"].into_iter().map(parse),
+        )
+        .unwrap();
+        assert_eq!(mt.rows.len(), 2);
+
+        let mut parsed = 0;
+        let res = MarkdownTable::try_from_iter(
+            Some(vec!["Name", "Age"]),
+            ["Jessica,28", "Dennis", "Bob,7"].into_iter().map(|line| {
+                parsed += 1;
+                parse(line)
+            }),
+        );
+        assert!(matches!(res, Err(LoadError::Parse(ref line)) if line == "Dennis"));
+        assert_eq!(parsed, 2);
+
+        let res =
+            MarkdownTable::try_from_iter(Some(vec!["Name"]), ["Jessica,28"].into_iter().map(parse));
+        assert!(matches!(
+            res,
+            Err(LoadError::Table(MarkdownTableError::InvalidRowLength(1, 2)))
+        ));
+    }
+
+    #[test]
+    fn fitting_to_width() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Description"]),
+            vec![vec!["Jessica", "Likes long walks on the beach"]],
+        )
+        .unwrap();
+        mt.fit_to_width(30);
+
+        assert!(mt
+            .to_string()
+            .lines()
+            .all(|line| line.chars().count() <= 30));
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Description      |\n| ------- | ---------------- |\n| Jessica | Likes long walk… |\n"
+        );
+
+        let mut mt = MarkdownTable::new(Some(vec!["Name"]), vec![vec!["Jessica"]]).unwrap();
+        mt.set_min_column_width(0, 20);
+        mt.fit_to_width(15);
+        assert_eq!(mt.rendered_width(), 15);
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn fitting_to_terminal() {
+        let rows: Vec<Vec<String>> = (0..3)
+            .map(|i| vec![i.to_string(), "x".repeat(100)])
+            .collect();
+        let mut mt = MarkdownTable::new(Some(vec!["N", "Text"]), rows).unwrap();
+
+        mt.fit_to_terminal_width(Some(40));
+        assert!(mt
+            .to_string()
+            .lines()
+            .all(|line| line.chars().count() <= 40));
+
+        mt.fit_to_terminal_width(None);
+        assert!(mt
+            .to_string()
+            .lines()
+            .all(|line| line.chars().count() <= 80));
+    }
+
+    #[test]
+    fn header_abbreviations() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age in years"]),
+            vec![vec!["Jessica", "28"]],
+        )
+        .unwrap();
+        mt.set_header_abbreviations(vec![(0, "N".to_string()), (1, "A".to_string())]);
+
+        assert_eq!(
+            mt.to_string(),
+            "| N       | A  |\n| ------- | -- |\n| Jessica | 28 |\n\n- N = Name\n- A = Age in years\n"
+        );
+
+        mt.set_header_abbreviations(Vec::new());
+        assert!(!mt.to_string().contains("- N = Name"));
+    }
+
+    #[test]
+    fn collapsing_repeats() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Group", "Name"]),
+            vec![vec!["A", "Jessica"], vec!["A", "Dennis"], vec!["B", "Bob"]],
+        )
+        .unwrap();
+        mt.set_collapse_repeats(0);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Group | Name    |\n| ----- | ------- |\n| A     | Jessica |\n| \"     | Dennis  |\n| B     | Bob     |\n"
+        );
+    }
+
+    #[test]
+    fn taking_rows() {
+        let rows = vec![
+            TableRow::from(vec!["Jessica", "28"]),
+            TableRow::from(vec!["Dennis", "22"]),
+        ];
+        let mt = MarkdownTable::new(Some(vec!["Name", "Age"]), rows.clone()).unwrap();
+        assert_eq!(mt.clone().take_rows(), rows);
+
+        let (header, taken) = mt.into_parts();
+        assert_eq!(header, Some(TableRow::from(vec!["Name", "Age"])));
+        assert_eq!(taken, rows);
+    }
+
+    #[test]
+    fn max_render_width() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Payload"]),
+            vec![vec!["Jessica".to_string(), "x".repeat(1_000_000)]],
+        )
+        .unwrap();
+        assert!(mt.clamped_columns().is_empty());
+
+        mt.set_max_render_width(10);
+        assert_eq!(mt.clamped_columns(), vec![1]);
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Payload    |\n| ------- | ---------- |\n| Jessica | xxxxxxxxx… |\n"
+        );
+
+        mt.set_min_column_width(0, 20);
+        mt.set_uniform_width(true);
+        assert_eq!(
+            mt.to_string(),
+            "| Name       | Payload    |\n| ---------- | ---------- |\n| Jessica    | xxxxxxxxx… |\n"
+        );
+
+        mt.set_ellipsis("...");
+        mt.set_max_render_width(2);
+        assert_eq!(mt.to_string(), "| .. | .. |\n| -- | -- |\n| .. | .. |\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn to_json() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Dennis", "22"]],
+        )
+        .unwrap();
+        let json: serde_json::Value = serde_json::from_str(&mt.to_json()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "Name": "Jessica", "Age": "28" },
+                { "Name": "Dennis", "Age": "22" }
+            ])
+        );
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert_eq!(headerless.to_json(), r#"[["Jessica","28"]]"#);
+    }
+
+    #[test]
+    fn retaining_rows() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["", "22"], vec!["Bob", "7"]],
+        )
+        .unwrap();
+        mt.retain_rows(|row| row.get(0).is_some_and(|cell| !cell.is_empty()));
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n| Bob     | 7   |\n"
+        );
+
+        let mut headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["a", "b"]]).unwrap();
+        headerless.retain_rows(|_| false);
+        assert_eq!(headerless.to_string(), "");
+        assert!(headerless.add_row(vec!["c"]).is_ok());
+    }
+
+    #[test]
+    fn collapsible() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        mt.set_collapsible("People & ages");
+
+        assert_eq!(
+            mt.to_string(),
+            "<details><summary>People &amp; ages</summary>\n\n| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n\n</details>\n"
+        );
+    }
+
+    #[test]
+    fn reading_alignment() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age", "City"]),
+            vec![vec!["Jessica", "28", "Utrecht"]],
+        )
+        .unwrap();
+        mt.set_alignment(1, Alignment::Right);
+
+        assert_eq!(mt.column_alignment(0), Some(Alignment::None));
+        assert_eq!(mt.column_alignment(1), Some(Alignment::Right));
+        assert_eq!(mt.column_alignment(2), Some(Alignment::None));
+        assert_eq!(mt.column_alignment(3), None);
+    }
+
+    #[test]
+    fn html_column_width_percent() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age", "City"]),
+            vec![vec!["Jessica", "28", "Utrecht"]],
+        )
+        .unwrap();
+        assert!(!mt.to_html().contains("<colgroup>"));
+
+        mt.set_column_width_percent(0, 50);
+        mt.set_column_width_percent(2, 30);
+        assert!(mt.to_html().starts_with(
+            "<table>\n<colgroup><col style=\"width:50%\"><col><col style=\"width:30%\"></colgroup>\n<thead>"
+        ));
+    }
+
+    #[test]
+    fn uniform_width() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age", "City"]),
+            vec![vec!["Jessica", "28", "Utrecht"]],
+        )
+        .unwrap();
+        mt.set_uniform_width(true);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age     | City    |\n| ------- | ------- | ------- |\n| Jessica | 28      | Utrecht |\n"
+        );
+    }
+
+    #[test]
+    fn rendering_lines() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Dennis", "22"]],
+        )
+        .unwrap();
+
+        let lines: Vec<String> = mt.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "| ------- | --- |");
+        assert_eq!(lines.join("\n") + "\n", mt.to_string());
+    }
+
+    #[test]
+    fn cell_links() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Crate", "Downloads"]),
+            vec![vec!["serde", "500M"], vec!["thiserror", "300M"]],
+        )
+        .unwrap();
+
+        assert!(mt
+            .set_cell_link(0, 0, "https://en.wikipedia.org/wiki/Serde_(disambiguation)")
+            .is_ok());
+        assert_eq!(
+            mt.to_string(),
+            "| Crate     | Downloads |\n| --------- | --------- |\n| [serde](https://en.wikipedia.org/wiki/Serde_\\(disambiguation\\))     | 500M      |\n| thiserror | 300M      |\n"
+        );
+
+        assert!(matches!(
+            mt.set_cell_link(0, 0, " "),
+            Err(MarkdownTableError::EmptyLinkUrl)
+        ));
+        assert!(matches!(
+            mt.set_cell_link(2, 0, "https://crates.io"),
+            Err(MarkdownTableError::RowOutOfRange(2, 2))
+        ));
+        assert!(matches!(
+            mt.set_cell_link(0, 2, "https://crates.io"),
+            Err(MarkdownTableError::ColumnOutOfRange(2, 2))
+        ));
+    }
+
+    #[test]
+    fn aggregating() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Dennis", ""], vec!["Bob", "7"]],
+        )
+        .unwrap();
+
+        assert_eq!(mt.aggregate(1, AggregateKind::Sum), Some(35.0));
+        assert_eq!(mt.aggregate(1, AggregateKind::Mean), Some(17.5));
+        assert_eq!(mt.aggregate(1, AggregateKind::Max), Some(28.0));
+        assert_eq!(mt.aggregate(1, AggregateKind::Min), Some(7.0));
+        assert_eq!(mt.aggregate(1, AggregateKind::Count), Some(2.0));
+        assert_eq!(mt.aggregate(0, AggregateKind::Sum), None);
+        assert_eq!(mt.aggregate(2, AggregateKind::Sum), None);
+    }
+
+    #[test]
+    fn auto_footer() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Item", "Price", "Qty"]),
+            vec![vec!["Apple", "1.5", "4"], vec!["Pear", "2", "10"]],
+        )
+        .unwrap();
+        mt.set_auto_footer(AggregateKind::Sum);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Item  | Price | Qty |\n| ----- | ----- | --- |\n| Apple | 1.5   | 4   |\n| Pear  | 2     | 10  |\n| ----- | ----- | --- |\n| Sum   | 3.5   | 14  |\n"
+        );
+
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Price"]),
+            vec![vec!["1.10"], vec!["2.2"], vec!["0"]],
+        )
+        .unwrap();
+        mt.set_auto_footer(AggregateKind::Sum);
+        assert!(mt.to_string().ends_with("| ----- |\n| 3.30  |\n"));
+
+        mt.set_auto_footer(AggregateKind::Mean);
+        assert!(mt.to_string().ends_with("| ----- |\n| 1.1   |\n"));
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Item", "Qty"]),
+            vec![vec!["Apple", "1"], vec!["Pear", "2"]],
+        )
+        .unwrap();
+        mt.set_auto_footer(AggregateKind::Sum);
+        assert!(mt
+            .with_row_numbers("#")
+            .to_string()
+            .ends_with("| Sum |       | 3   |\n"));
+    }
+
+    #[test]
+    fn replacing_text() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["foo", "Bar"]),
+            vec![vec!["foo", "foofoo"], vec!["food", "bar"]],
+        )
+        .unwrap();
+
+        assert_eq!(mt.replace_all("foo", "bar", false), 4);
+        assert_eq!(mt.get_cell(0, 1), Some("barbar"));
+        assert_eq!(mt.get_cell(1, 0), Some("bard"));
+        assert_eq!(mt.header().unwrap().get(0), Some("foo"));
+
+        assert_eq!(mt.replace_all("foo", "bar", true), 1);
+        assert_eq!(mt.header().unwrap().get(0), Some("bar"));
+        assert_eq!(mt.replace_all("", "bar", true), 0);
+    }
+
+    #[test]
+    fn html_column_groups() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Id", "Name", "Age", "City"]),
+            vec![vec!["1", "Jessica", "28", "Utrecht"]],
+        )
+        .unwrap();
+
+        assert!(mt.set_column_group(1, 3, "Person").is_ok());
+        assert!(mt.set_column_group(3, 4, "Place").is_ok());
+        assert!(mt.to_html().contains(
+            "<thead>\n<tr><th></th><th colspan=\"2\">Person</th><th colspan=\"1\">Place</th></tr>\n<tr><th"
+        ));
+
+        mt.set_column_visible(2, false);
+        assert!(mt.to_html().contains(
+            "<thead>\n<tr><th></th><th colspan=\"1\">Person</th><th colspan=\"1\">Place</th></tr>\n<tr><th"
+        ));
+        mt.set_column_visible(2, true);
+
+        assert!(matches!(
+            mt.set_column_group(2, 4, "Overlap"),
+            Err(MarkdownTableError::InvalidColumnGroup(2, 4))
+        ));
+        assert!(matches!(
+            mt.set_column_group(0, 5, "Everything"),
+            Err(MarkdownTableError::InvalidColumnGroup(0, 5))
+        ));
+        assert!(matches!(
+            mt.set_column_group(0, 0, "Nothing"),
+            Err(MarkdownTableError::InvalidColumnGroup(0, 0))
+        ));
+
+        assert!(mt.with_row_numbers("#").to_html().contains(
+            "<thead>\n<tr><th></th><th></th><th colspan=\"2\">Person</th><th colspan=\"1\">Place</th></tr>\n"
+        ));
+    }
+
+    #[test]
+    fn preview() {
+        let rows: Vec<Vec<String>> = (1..=10).map(|i| vec![i.to_string()]).collect();
+        let mt = MarkdownTable::new(Some(vec!["N"]), rows).unwrap();
+
+        assert_eq!(
+            mt.preview(2, 2).to_string(),
+            "| N  |\n| -- |\n| 1  |\n| 2  |\n| …  |\n| 9  |\n| 10 |\n"
+        );
+        assert_eq!(mt.preview(5, 5).to_string(), mt.to_string());
+    }
+
+    #[test]
+    fn row_to_map() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+
+        let map = mt.row_to_map(0).unwrap();
+        assert_eq!(map.get("Age").map(String::as_str), Some("28"));
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec!["Age", "Name"]);
+        assert!(mt.row_to_map(2).is_none());
+
+        let headerless = MarkdownTable::new(None::<TableRow>, vec![vec!["Jessica", "28"]]).unwrap();
+        assert!(headerless.row_to_map(0).is_none());
+    }
+
+    #[test]
+    fn number_grouping() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Number"]),
+            vec![vec!["1234567"], vec!["-1234.5"], vec!["123"], vec!["many"]],
+        )
+        .unwrap();
+        mt.set_number_grouping(0, ',');
+
+        assert_eq!(
+            mt.to_string(),
+            "| Number    |\n| --------- |\n| 1,234,567 |\n| -1,234.5  |\n| 123       |\n| many      |\n"
+        );
+    }
+
+    #[test]
+    fn concat() {
+        let tables: Vec<MarkdownTable> = (0..3)
+            .map(|i| {
+                MarkdownTable::new(
+                    None::<TableRow>,
+                    vec![vec![format!("{i}a"), format!("{i}b")]; i + 1],
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let mt = MarkdownTable::concat(tables).unwrap();
+        assert!(mt.header().is_none());
+        assert_eq!(mt.rows().count(), 6);
+
+        let narrow = MarkdownTable::new(None::<TableRow>, vec![vec!["alone"]]).unwrap();
+        assert!(matches!(
+            MarkdownTable::concat(vec![mt, narrow]),
+            Err(MarkdownTableError::ColumnCountMismatch(2, 1))
+        ));
+        assert!(matches!(
+            MarkdownTable::concat(Vec::new()),
+            Err(MarkdownTableError::NoRowsSpecified)
+        ));
+    }
+
+    #[test]
+    fn column_wrap() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Id", "A", "B", "C", "D"]),
+            vec![vec!["1", "a", "b", "c", "d"]],
+        )
+        .unwrap();
+        mt.set_column_wrap(3);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Id | A | B |\n| -- | - | - |\n| 1  | a | b |\n\n| C | D |\n| - | - |\n| c | d |\n"
+        );
+
+        mt.set_wrap_key_column(0);
+        assert_eq!(
+            mt.to_string(),
+            "| Id | A | B |\n| -- | - | - |\n| 1  | a | b |\n\n| Id | C | D |\n| -- | - | - |\n| 1  | c | d |\n"
+        );
+
+        assert!(mt
+            .with_row_numbers("#")
+            .to_string()
+            .starts_with("| Id | # | A |\n"));
+
+        mt.set_header_abbreviations(vec![(0, "I".to_string())]);
+        assert_eq!(
+            mt.to_string(),
+            "| I | A | B |\n| - | - | - |\n| 1 | a | b |\n\n| I | C | D |\n| - | - | - |\n| 1 | c | d |\n\n- I = Id\n"
+        );
+    }
+
+    #[test]
+    fn is_numeric_column() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age", "Score"]),
+            vec![vec!["Jessica", "28", "9.5"], vec!["Jonah", "", "n/a"]],
+        )
+        .unwrap();
+
+        assert_eq!(mt.is_numeric_column(0), Some(false));
+        assert_eq!(mt.is_numeric_column(1), Some(true));
+        assert_eq!(mt.is_numeric_column(2), Some(false));
+        assert_eq!(mt.is_numeric_column(3), None);
+    }
+
+    #[test]
+    fn cell_renderer() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+        mt.set_cell_renderer(|row, col, text| format!("{row},{col}: {text}"));
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name         | Age     |\n| ------------ | ------- |\n| 0,0: Jessica | 0,1: 28 |\n| 1,0: Jonah   | 1,1: 22 |\n"
+        );
+
+        let mut mt = MarkdownTable::headerless(vec![vec!["a", "b", "c", "d"]]).unwrap();
+        mt.set_cell_renderer(|_, col, text| format!("{text}@{col}"));
+        mt.set_column_wrap(2);
+        assert_eq!(mt.to_string(), "| a@0 | b@1 |\n\n| c@2 | d@3 |\n");
+    }
+
+    #[test]
+    fn tab_width() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Key", "Value"]),
+            vec![vec!["a\tb", "x"], vec!["abcde\tf", "y"]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            mt.to_string(),
+            "| Key       | Value |\n| --------- | ----- |\n| a   b     | x     |\n| abcde   f | y     |\n"
+        );
+
+        mt.set_tab_width(2);
+        assert_eq!(
+            mt.to_string(),
+            "| Key     | Value |\n| ------- | ----- |\n| a b     | x     |\n| abcde f | y     |\n"
+        );
+    }
+
+    #[test]
+    fn records() {
+        #[derive(Debug, PartialEq)]
+        struct User {
+            name: String,
+            age: u32,
+        }
+
+        impl FromTableRow for User {
+            type Error = String;
+
+            fn from_table_row(row: &TableRow) -> std::result::Result<Self, Self::Error> {
+                let name = row.get(0).ok_or("missing name")?.to_string();
+                let age = row.get(1).ok_or("missing age")?;
+                let age = age.parse().map_err(|_| format!("invalid age {age}"))?;
+
+                Ok(User { name, age })
+            }
+        }
+
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+        assert_eq!(
+            mt.records::<User>().unwrap(),
+            vec![
+                User {
+                    name: "Jessica".to_string(),
+                    age: 28
+                },
+                User {
+                    name: "Jonah".to_string(),
+                    age: 22
+                },
+            ]
+        );
+
+        let invalid =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "old"]]).unwrap();
+        assert_eq!(
+            invalid.records::<User>(),
+            Err("invalid age old".to_string())
+        );
+    }
+
+    #[test]
+    fn sort_by_columns() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Team", "Score"]),
+            vec![
+                vec!["b", "3"],
+                vec!["a", "9"],
+                vec!["b", "10"],
+                vec!["a", "12"],
+            ],
+        )
+        .unwrap();
+
+        assert!(mt
+            .sort_by_columns(&[(0, SortOrder::Ascending), (1, SortOrder::Descending)])
+            .is_ok());
+        assert_eq!(
+            mt.rows().map(|row| row.get(1).unwrap()).collect::<Vec<_>>(),
+            vec!["12", "9", "10", "3"]
+        );
+
+        let mut mixed = MarkdownTable::new(
+            Some(vec!["Value"]),
+            vec![vec!["10"], vec!["b"], vec!["9"], vec!["a"], vec!["2"]],
+        )
+        .unwrap();
+        assert!(mixed.sort_by_columns(&[(0, SortOrder::Ascending)]).is_ok());
+        assert_eq!(
+            mixed
+                .rows()
+                .map(|row| row.get(0).unwrap())
+                .collect::<Vec<_>>(),
+            vec!["2", "9", "10", "a", "b"]
+        );
+
+        assert!(matches!(
+            mt.sort_by_columns(&[(2, SortOrder::Ascending)]),
+            Err(MarkdownTableError::ColumnOutOfRange(2, 2))
+        ));
+    }
+
+    #[test]
+    fn to_fenced() {
+        let mt = MarkdownTable::new(Some(vec!["Code"]), vec![vec!["`a`"]]).unwrap();
+        assert_eq!(
+            mt.to_fenced(Some("markdown")),
+            "```markdown\n| Code |\n| ---- |\n| `a`  |\n```\n"
+        );
+
+        let mt = MarkdownTable::new(Some(vec!["Code"]), vec![vec!["````"]]).unwrap();
+        assert_eq!(
+            mt.to_fenced(None),
+            "`````\n| Code |\n| ---- |\n| ```` |\n`````\n"
+        );
+    }
+
+    #[test]
+    fn headerless() {
+        let mt =
+            MarkdownTable::headerless(vec![vec!["Jessica", "28"], vec!["Jonah", "22"]]).unwrap();
+        assert!(mt.header().is_none());
+        assert_eq!(mt.to_string(), "| Jessica | 28 |\n| Jonah   | 22 |\n");
+
+        assert!(matches!(
+            MarkdownTable::headerless(Vec::<TableRow>::new()),
+            Err(MarkdownTableError::NoRowsSpecified)
+        ));
+    }
+
+    #[test]
+    fn render_fmt() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        mt.set_collapsible("People");
+
+        let mut buf = String::new();
+        assert!(mt.render_fmt(&mut buf).is_ok());
+        assert_eq!(buf, mt.to_string());
+    }
+
+    #[test]
+    fn column_visible() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Secret", "Age"]),
+            vec![vec!["Jessica", "hunter2", "28"]],
+        )
+        .unwrap();
+        mt.set_column_visible(1, false);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n"
+        );
+        assert_eq!(mt.column_count(), 3);
+        assert_eq!(mt.get_cell(0, 1), Some("hunter2"));
+        assert_eq!(mt.rendered_width(), 17);
+        assert!(!mt.to_html().contains("hunter2"));
+        assert!(mt.to_tsv().contains("hunter2"));
+
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = seen.clone();
+        mt.set_cell_renderer(move |_, col, content| {
+            record.lock().unwrap().push(col);
+            content.to_string()
+        });
+        mt.to_string();
+        assert_eq!(*seen.lock().unwrap(), vec![0, 2]);
+
+        mt.fit_to_width(16);
+        assert_eq!(mt.rendered_width(), 16);
+        assert!(mt.to_string().contains("| Jessi… | 28  |"));
+
+        mt.set_column_visible(1, true);
+        assert!(mt.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn distinct_count() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Grade", "Name"]),
+            vec![
+                vec!["A", "Jessica"],
+                vec!["A", "Jonah"],
+                vec!["B", "Dennis"],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(mt.distinct_count(0), Some(2));
+        assert_eq!(mt.distinct_count(1), Some(3));
+        assert_eq!(mt.distinct_count(2), None);
+    }
+
+    #[test]
+    fn separator_matches_column_width() {
+        for (width, alignment) in [
+            (1, Alignment::None),
+            (1, Alignment::Left),
+            (1, Alignment::Right),
+            (1, Alignment::Center),
+            (2, Alignment::Center),
+            (4, Alignment::Center),
+            (6, Alignment::Right),
+        ] {
+            let mut mt =
+                MarkdownTable::new(Some(vec!["x".repeat(width)]), vec![vec!["a"]]).unwrap();
+            mt.set_alignment(0, alignment);
+
+            let rendered = mt.to_string();
+            let lines: Vec<&str> = rendered.lines().collect();
+            assert_eq!(
+                lines[0].len(),
+                lines[1].len(),
+                "{alignment:?} at width {width}"
+            );
+            assert!(lines[1].contains('-'));
+        }
+
+        let mut mt = MarkdownTable::new(Some(vec!["A"]), vec![vec!["1"]]).unwrap();
+        mt.set_alignment(0, Alignment::Center);
+        assert_eq!(mt.to_string(), "|  A  |\n| :-: |\n|  1  |\n");
+    }
+
+    #[test]
+    fn into_iter() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+
+        let mut names = Vec::new();
+        for row in mt {
+            names.push(row.get(0).unwrap().to_string());
+        }
+        assert_eq!(names, vec!["Jessica", "Jonah"]);
+    }
+
+    #[test]
+    fn force_separator() {
+        let mut mt = MarkdownTable::headerless(vec![vec!["Jessica", "28"]]).unwrap();
+        mt.set_force_separator(true);
+
+        assert_eq!(mt.to_string(), "| ------- | -- |\n| Jessica | 28 |\n");
+    }
+
+    #[test]
+    fn truncation_position() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Path"]), vec![vec!["/very/long/path/file"]]).unwrap();
+
+        mt.set_truncation(0, 11, TruncatePos::Middle);
+        assert!(mt.to_string().contains("| /very…/file |"));
+
+        mt.set_truncation(0, 11, TruncatePos::Start);
+        assert!(mt.to_string().contains("| …/path/file |"));
+
+        mt.set_truncation(0, 11, TruncatePos::End);
+        assert!(mt.to_string().contains("| /very/long… |"));
+    }
+
+    #[test]
+    fn builder() {
+        let mt = MarkdownTable::builder()
+            .header(vec!["Name", "Age"])
+            .row(vec!["Jessica", "28"])
+            .build()
+            .unwrap();
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n"
+        );
+
+        assert!(matches!(
+            MarkdownTable::builder()
+                .header(vec!["Name", "Age"])
+                .row(vec!["Jessica"])
+                .build(),
+            Err(MarkdownTableError::InvalidRowLength(2, 1))
+        ));
+    }
+
+    #[test]
+    fn builder_max_columns() {
+        let builder = MarkdownTable::builder()
+            .header(vec!["A", "B", "C"])
+            .row(vec!["1", "2", "3"]);
+
+        assert!(builder.clone().max_columns(3).build().is_ok());
+        assert!(matches!(
+            builder.max_columns(2).build(),
+            Err(MarkdownTableError::TooManyColumns(2, 3))
+        ));
+    }
+
+    #[test]
+    fn builder_unchecked() {
+        let mt = MarkdownTable::builder()
+            .header(vec!["N", "Square"])
+            .rows((0..10_000).map(|n: u64| vec![n, n * n]))
+            .unchecked()
+            .build()
+            .unwrap();
+
+        let rendered = mt.to_string();
+        assert_eq!(rendered.lines().count(), 10_002);
+        assert!(rendered.ends_with("| 9999 | 99980001 |\n"));
+    }
+
+    #[test]
+    fn builder_unchecked_ragged() {
+        let mut mt = MarkdownTable::builder()
+            .header(vec!["Name", "Age"])
+            .row(vec!["Jessica", "28"])
+            .row(vec!["Jonah"])
+            .row(vec!["Dennis", "22"])
+            .unchecked()
+            .build()
+            .unwrap();
+
+        assert_eq!(mt.is_numeric_column(1), Some(true));
+        assert_eq!(mt.distinct_count(1), Some(3));
+        assert_eq!(mt.widest_cell(1), Some("28"));
+        assert_eq!(mt.infer_column_type(1), Some(ColumnType::Integer));
+        assert_eq!(mt.group_by_column(1).unwrap().len(), 3);
+
+        assert!(mt.sort_by_columns(&[(1, SortOrder::Ascending)]).is_ok());
+        assert_eq!(
+            mt.rows().map(|row| row.get(0).unwrap()).collect::<Vec<_>>(),
+            vec!["Dennis", "Jessica", "Jonah"]
+        );
+    }
+
+    #[test]
+    fn code_column() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Option", "Meaning"]),
+            vec![vec!["--verbose", "More output"], vec!["a`b", "Backtick"]],
+        )
+        .unwrap();
+        mt.set_code_column(0);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Option      | Meaning     |\n| ----------- | ----------- |\n| `--verbose` | More output |\n| ``a`b``     | Backtick    |\n"
+        );
+    }
+
+    #[test]
+    fn html_caption() {
+        let mut mt = MarkdownTable::new(Some(vec!["Name"]), vec![vec!["Jessica"]]).unwrap();
+        mt.set_caption("Users <active>");
+
+        assert!(mt
+            .to_html()
+            .starts_with("<table>\n<caption>Users &lt;active&gt;</caption>\n<thead>"));
+    }
+
+    #[test]
+    fn rotate_rows() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["N"]), vec![vec!["1"], vec!["2"], vec!["3"]]).unwrap();
+        let order = |mt: &MarkdownTable| {
+            mt.rows()
+                .map(|row| row.get(0).unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        mt.rotate_rows(1);
+        assert_eq!(order(&mt), vec!["3", "1", "2"]);
+
+        mt.rotate_rows(-4);
+        assert_eq!(order(&mt), vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn strip_ansi_for_width() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Status"]),
+            vec![vec!["\x1b[31mfailed\x1b[0m"], vec!["passed"]],
+        )
+        .unwrap();
+        mt.set_strip_ansi_for_width(true);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Status |\n| ------ |\n| \x1b[31mfailed\x1b[0m |\n| passed |\n"
+        );
+
+        mt.set_max_column_width(0, 4);
+        assert_eq!(
+            mt.to_string(),
+            "| Sta… |\n| ---- |\n| \x1b[31mfai…\x1b[0m |\n| pas… |\n"
+        );
+    }
+
+    #[test]
+    fn widest_cell() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name"]),
+            vec![vec!["Jonah"], vec!["Jessica"], vec!["Dennise"]],
+        )
+        .unwrap();
+
+        assert_eq!(mt.widest_cell(0), Some("Jessica"));
+        assert_eq!(mt.widest_cell(1), None);
+    }
+
+    #[test]
+    fn empty_message() {
+        let mut mt = MarkdownTable::new(Some(vec!["Name", "Age"]), Vec::<TableRow>::new()).unwrap();
+        mt.set_empty_message("_No data_");
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name      | Age |\n| --------- | --- |\n| _No data_ |     |\n"
+        );
+
+        assert!(mt.add_row(vec!["Jessica", "28"]).is_ok());
+        assert!(!mt.to_string().contains("_No data_"));
+    }
+
+    #[test]
+    fn from_enumerated() {
+        let mt = MarkdownTable::from_enumerated(("#", "Name"), vec!["Jessica", "Jonah"], 1);
+
+        assert_eq!(
+            mt.to_string(),
+            "| # | Name    |\n| - | ------- |\n| 1 | Jessica |\n| 2 | Jonah   |\n"
+        );
+    }
+
+    #[test]
+    fn to_rows_vec() {
+        let mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+
+        assert_eq!(
+            mt.to_rows_vec(),
+            (
+                Some(vec!["Name".to_string(), "Age".to_string()]),
+                vec![vec!["Jessica".to_string(), "28".to_string()]]
+            )
+        );
+    }
+
+    #[test]
+    fn drop_empty_rows() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![
+                vec!["Jessica", "28"],
+                vec!["", " "],
+                vec!["Jonah", ""],
+                vec!["", ""],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(mt.drop_empty_rows(), 2);
+        assert_eq!(
+            mt.rows().map(|row| row.get(0).unwrap()).collect::<Vec<_>>(),
+            vec!["Jessica", "Jonah"]
+        );
+        assert_eq!(mt.drop_empty_rows(), 0);
+    }
+
+    #[test]
+    fn footer_header() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+        mt.set_footer_header(true);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n| Jonah   | 22  |\n| ------- | --- |\n| Name    | Age |\n"
+        );
+
+        mt.set_emit_separator(false);
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| Jessica | 28  |\n| Jonah   | 22  |\n| Name    | Age |\n"
+        );
+    }
+
+    #[test]
+    fn scaled_column() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Size", "Count"]),
+            vec![
+                vec!["1048576", "1536"],
+                vec!["512", "2500000"],
+                vec!["-", "999"],
+                vec!["1048575", "999999"],
+            ],
+        )
+        .unwrap();
+        mt.set_scaled_column(0, ScaleBase::Binary);
+        mt.set_scaled_column(1, ScaleBase::Decimal);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Size    | Count |\n| ------- | ----- |\n| 1.0 MiB | 1.5 k |\n| 512     | 2.5 M |\n| -       | 999   |\n| 1.0 MiB | 1.0 M |\n"
+        );
+    }
+
+    #[test]
+    fn diff_stats() {
+        let old = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+        let new = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![
+                vec!["Jessica", "29"],
+                vec!["Jonah", "22"],
+                vec!["Dennis", "30"],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            old.diff_stats(&new).unwrap(),
+            DiffStats {
+                added_rows: 1,
+                removed_rows: 0,
+                changed_cells: 1,
+            }
+        );
+        assert_eq!(new.diff_stats(&old).unwrap().removed_rows, 1);
+
+        let narrow = MarkdownTable::new(Some(vec!["Name"]), vec![vec!["Jessica"]]).unwrap();
+        assert!(matches!(
+            old.diff_stats(&narrow),
+            Err(MarkdownTableError::ColumnCountMismatch(2, 1))
+        ));
+    }
+
+    #[test]
+    fn with_columns() {
+        assert!(MarkdownTable::with_columns(
+            2,
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"]]
+        )
+        .is_ok());
+
+        assert!(matches!(
+            MarkdownTable::with_columns(
+                2,
+                None::<TableRow>,
+                vec![
+                    vec!["Jessica", "28", "Utrecht"],
+                    vec!["Jonah", "22", "Delft"]
+                ]
+            ),
+            Err(MarkdownTableError::InvalidRowLength(2, 3))
+        ));
+        assert!(matches!(
+            MarkdownTable::with_columns(3, Some(vec!["Name", "Age"]), Vec::<TableRow>::new()),
+            Err(MarkdownTableError::InvalidRowLength(3, 2))
+        ));
+    }
+
+    #[test]
+    fn zero_pad_column() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Id"]),
+            vec![vec!["1"], vec!["42"], vec!["100"], vec!["-7"], vec!["1.5"]],
+        )
+        .unwrap();
+        mt.set_zero_pad_column(0, 3);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Id   |\n| ---- |\n| 001  |\n| 042  |\n| 100  |\n| -007 |\n| 1.5  |\n"
+        );
+    }
+
+    #[test]
+    fn row_strings() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            mt.row_strings(),
+            vec!["| Jessica | 28  |", "| Jonah   | 22  |"]
+        );
+    }
+
+    #[test]
+    fn parts() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+
+        assert_eq!(
+            mt.parts(),
+            TableParts {
+                header: Some("| Name    | Age |".to_string()),
+                separator: Some("| ------- | --- |".to_string()),
+                rows: vec![
+                    "| Jessica | 28  |".to_string(),
+                    "| Jonah   | 22  |".to_string()
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn row_separators() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "22"]],
+        )
+        .unwrap();
+        mt.set_row_separators(true);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n| ------- | --- |\n| Jonah   | 22  |\n"
+        );
+    }
+
+    #[test]
+    fn typed_cells() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+
+        assert!(mt.set_cell_typed(0, 1, 29u32).is_ok());
+        assert_eq!(mt.get_cell_typed::<u32>(0, 1), Some(Ok(29)));
+        assert!(matches!(mt.get_cell_typed::<u32>(0, 0), Some(Err(_))));
+        assert!(mt.get_cell_typed::<u32>(1, 1).is_none());
+    }
+
+    #[test]
+    fn to_plain() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jonah", "7"]],
+        )
+        .unwrap();
+        mt.set_alignment(1, Alignment::Right);
+
+        let plain = mt.to_plain();
+        assert!(!plain.contains('|'));
+        assert_eq!(
+            plain,
+            "Name     Age\n-------  ---\nJessica   28\nJonah      7\n"
+        );
+
+        mt.set_plain_underline(false);
+        assert_eq!(mt.to_plain(), "Name     Age\nJessica   28\nJonah      7\n");
+
+        assert!(mt.set_super_header(vec!["Group long label", ""]).is_ok());
+        assert!(mt.set_cell_link(0, 0, "http://x").is_ok());
+        mt.set_auto_footer(AggregateKind::Sum);
+        assert_eq!(mt.to_plain(), "Name     Age\nJessica   28\nJonah      7\n");
+    }
+
+    #[test]
+    fn chunk_rows() {
+        let rows: Vec<Vec<String>> = (1..=5).map(|i| vec![i.to_string()]).collect();
+        let mt = MarkdownTable::new(Some(vec!["N"]), rows).unwrap();
+
+        let chunks = mt.chunk_rows(2);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].to_string(), "| N |\n| - |\n| 1 |\n| 2 |\n");
+        assert_eq!(chunks[2].to_string(), "| N |\n| - |\n| 5 |\n");
+
+        assert_eq!(mt.chunk_rows(0).len(), 1);
+    }
+
+    #[test]
+    fn column_padding() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        mt.set_column_padding(0, 2, 0);
+
+        assert_eq!(
+            mt.to_string(),
+            "|  Name   | Age |\n|  -------| --- |\n|  Jessica| 28  |\n"
+        );
+        assert_eq!(mt.rendered_width(), 17);
+    }
+
+    #[test]
+    fn add_computed_column() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["First", "Last"]),
+            vec![vec!["Jessica", "Jones"], vec!["Jonah", "Gold"]],
+        )
+        .unwrap();
+
+        assert!(mt
+            .add_computed_column(Some("Full".to_string()), |row| {
+                format!("{} {}", row.get(0).unwrap(), row.get(1).unwrap())
+            })
+            .is_ok());
+        assert_eq!(mt.get_by_name(0, "Full"), Some("Jessica Jones"));
+        assert_eq!(mt.get_cell(1, 2), Some("Jonah Gold"));
+
+        assert!(matches!(
+            mt.add_computed_column(None, |_| String::new()),
+            Err(MarkdownTableError::MissingColumnHeader)
+        ));
+
+        let mut headerless = MarkdownTable::headerless(vec![vec!["a"]]).unwrap();
+        assert!(matches!(
+            headerless.add_computed_column(Some("B".to_string()), |_| String::new()),
+            Err(MarkdownTableError::UnexpectedHeader)
+        ));
+    }
+
+    #[test]
+    fn try_render() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Name", "Age"]), vec![vec!["Jessica", "28"]]).unwrap();
+        assert_eq!(mt.try_render().unwrap(), mt.to_string());
+
+        mt.set_alignment(3, Alignment::Right);
+        assert!(matches!(
+            mt.try_render(),
+            Err(MarkdownTableError::ColumnOutOfRange(3, 2))
+        ));
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica | 28  |\n"
+        );
+
+        let malformed = MarkdownTable::builder()
+            .header(vec!["Name", "Age"])
+            .row(vec!["Jessica"])
+            .unchecked()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            malformed.try_render(),
+            Err(MarkdownTableError::InvalidRowLength(2, 1))
+        ));
+        assert_eq!(
+            malformed.to_string(),
+            "| Name    | Age |\n| ------- | --- |\n| Jessica |     |\n"
+        );
+    }
+
+    #[test]
+    fn infer_column_type() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Age", "Score", "Active", "Note", "Empty"]),
+            vec![
+                vec!["28", "9.5", "true", "12", ""],
+                vec!["22", "7", "False", "n/a", " "],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(mt.infer_column_type(0), Some(ColumnType::Integer));
+        assert_eq!(mt.infer_column_type(1), Some(ColumnType::Float));
+        assert_eq!(mt.infer_column_type(2), Some(ColumnType::Boolean));
+        assert_eq!(mt.infer_column_type(3), Some(ColumnType::Text));
+        assert_eq!(mt.infer_column_type(4), Some(ColumnType::Text));
+        assert_eq!(mt.infer_column_type(5), None);
+    }
+
+    #[test]
+    fn group_by_column() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Team", "Name"]),
+            vec![
+                vec!["Red", "Jessica"],
+                vec!["Blue", "Jonah"],
+                vec!["Red", "Dennis"],
+            ],
+        )
+        .unwrap();
+
+        let groups = mt.group_by_column(0).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Red");
+        assert_eq!(
+            groups[0].1.to_string(),
+            "| Team | Name    |\n| ---- | ------- |\n| Red  | Jessica |\n| Red  | Dennis  |\n"
+        );
+        assert_eq!(groups[1].0, "Blue");
+        assert_eq!(groups[1].1.rows().count(), 1);
+
+        assert!(matches!(
+            mt.group_by_column(2),
+            Err(MarkdownTableError::ColumnOutOfRange(2, 2))
+        ));
+    }
+
+    #[test]
+    fn custom_ellipsis() {
+        let mut mt =
+            MarkdownTable::new(Some(vec!["Path"]), vec![vec!["/very/long/path/file"]]).unwrap();
+        mt.set_max_column_width(0, 8);
+        mt.set_ellipsis("...");
+
+        assert_eq!(mt.to_string(), "| Path     |\n| -------- |\n| /very... |\n");
+        assert_eq!(mt.rendered_width(), 12);
+
+        mt.set_max_column_width(0, 2);
+        assert_eq!(mt.to_string(), "| .. |\n| -- |\n| .. |\n");
+        assert_eq!(mt.rendered_width(), 6);
+    }
+
+    #[test]
+    fn html_cell_colspan() {
+        let mut mt =
+            MarkdownTable::headerless(vec![vec!["Total", "", "50"], vec!["a", "b", "c"]]).unwrap();
+
+        assert!(mt.set_cell_colspan(0, 0, 2).is_ok());
+        let html = mt.to_html();
+        assert!(html.contains("<tr><td colspan=\"2\">Total</td><td>50</td></tr>\n"));
+        assert!(html.contains("<tr><td>a</td><td>b</td><td>c</td></tr>\n"));
+
+        assert!(matches!(
+            mt.set_cell_colspan(0, 2, 2),
+            Err(MarkdownTableError::ColumnOutOfRange(3, 3))
+        ));
+        assert!(matches!(
+            mt.set_cell_colspan(2, 0, 2),
+            Err(MarkdownTableError::RowOutOfRange(2, 2))
+        ));
+    }
+
+    #[test]
+    fn html_cell_colspan_copies() {
+        let mut mt =
+            MarkdownTable::headerless(vec![vec!["a", "b"], vec!["c", "d"], vec!["e", "f"]])
+                .unwrap();
+        assert!(mt.set_cell_colspan(0, 0, 2).is_ok());
+        assert!(mt.set_cell_colspan(2, 0, 2).is_ok());
+
+        let chunks = mt.chunk_rows(2);
+        assert_eq!(
+            chunks[0].to_html(),
+            "<table>\n<tbody>\n<tr><td colspan=\"2\">a</td></tr>\n<tr><td>c</td><td>d</td></tr>\n</tbody>\n</table>\n"
+        );
+        assert_eq!(
+            chunks[1].to_html(),
+            "<table>\n<tbody>\n<tr><td colspan=\"2\">e</td></tr>\n</tbody>\n</table>\n"
+        );
+
+        assert!(mt.set_cell_colspan(2, 0, 1).is_ok());
+        assert!(mt.chunk_rows(2)[1]
+            .to_html()
+            .contains("<tr><td>e</td><td>f</td></tr>"));
+        assert!(mt.set_cell_colspan(1, 0, 2).is_ok());
+        assert!(mt
+            .preview(1, 1)
+            .to_html()
+            .contains("<tr><td>…</td><td>…</td></tr>"));
+        assert!(mt
+            .with_row_numbers("#")
+            .to_html()
+            .contains("<tr><td>1</td><td colspan=\"2\">a</td></tr>"));
+    }
+
+    #[test]
+    fn nbsp_padding() {
+        let mut mt = MarkdownTable::new(Some(vec!["Name"]), vec![vec!["Jo"]]).unwrap();
+        mt.set_nbsp_padding(true);
+
+        assert_eq!(
+            mt.to_string(),
+            "| Name |\n| ---- |\n| Jo\u{00A0}\u{00A0} |\n"
+        );
+    }
+
+    #[test]
+    fn from_tuples() {
+        let mt = MarkdownTable::from_tuples3(
+            Some(vec!["Name", "Age", "Admin"]),
+            vec![("Jessica", 28, true), ("Jonah", 22, false)],
+        )
+        .unwrap();
+        assert_eq!(
+            mt.to_string(),
+            "| Name    | Age | Admin |\n| ------- | --- | ----- |\n| Jessica | 28  | true  |\n| Jonah   | 22  | false |\n"
+        );
+
+        let mt = MarkdownTable::from_tuples2(None::<TableRow>, vec![("a", 1.5)]).unwrap();
+        assert_eq!(mt.to_string(), "| a | 1.5 |\n");
+
+        assert!(matches!(
+            MarkdownTable::from_tuples2(Some(vec!["Name"]), vec![("a", 1)]),
+            Err(MarkdownTableError::InvalidRowLength(1, 2))
+        ));
+    }
+
+    #[test]
+    fn fixed_widths() {
+        let mut mt = MarkdownTable::new(
+            Some(vec!["Name", "Age"]),
+            vec![vec!["Jessica", "28"], vec!["Jo", "7"]],
+        )
+        .unwrap();
+
+        assert!(mt.set_fixed_widths(vec![5, 3]).is_ok());
+        let rendered = mt.to_string();
+        for line in rendered.lines() {
+            let cells: Vec<&str> = line.split('|').collect();
+            assert_eq!(cells[1].chars().count(), 7, "{line}");
+            assert_eq!(cells[2].chars().count(), 5, "{line}");
+        }
+        assert!(rendered.contains("| Jess… | 28  |"));
+
+        assert!(matches!(
+            mt.set_fixed_widths(vec![5]),
+            Err(MarkdownTableError::ColumnCountMismatch(2, 1))
+        ));
+
+        mt.set_alignment(1, Alignment::Center);
+        assert!(matches!(
+            mt.set_fixed_widths(vec![5, 2]),
+            Err(MarkdownTableError::FixedWidthTooNarrow(1, 2))
+        ));
+        mt.set_ellipsis("...");
+        assert!(matches!(
+            mt.set_fixed_widths(vec![2, 3]),
+            Err(MarkdownTableError::FixedWidthTooNarrow(0, 2))
+        ));
+    }
+
+    #[test]
+    fn cells_matching() {
+        let mt = MarkdownTable::new(
+            Some(vec!["Name", "City"]),
+            vec![
+                vec!["Jessica", "Utrecht"],
+                vec!["Jonah", "Delft"],
+                vec!["Dennis", "Rotterdam"],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(mt.cells_matching("ht"), vec![(0, 1)]);
+        assert_eq!(
+            mt.cells_matching("e"),
+            vec![(0, 0), (0, 1), (1, 1), (2, 0), (2, 1)]
+        );
+        assert_eq!(mt.cells_matching("De"), vec![(1, 1), (2, 0)]);
+        assert!(mt.cells_matching("Name").is_empty());
+    }
+
+    #[test]
+    fn to_tsv() {
+        let dd = vec![
+            DummyRow {
+                data: vec!["a".to_string(), "b".to_string()],
+            },
+            DummyRow {
+                data: vec!["c\td".to_string(), "e".to_string()],
+            },
+        ];
 
         let mt = MarkdownTable::new(Some(vec!["Hoi".to_string(), "Bye".to_string()]), dd).unwrap();
 
-        println!("{}", mt);
+        assert_eq!(mt.to_tsv(), "Hoi\tBye\na\tb\nc d\te\n");
     }
 }